@@ -0,0 +1,129 @@
+//! Arrays with an explicit alignment and a custom allocator.
+
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::array::MemoryOwner;
+
+use std::alloc::{self, GlobalAlloc, Layout};
+use std::mem::{align_of, size_of};
+use std::panic::AssertUnwindSafe;
+
+/// A block of memory that was claimed from an allocator and will be given back to that
+/// allocator when it is dropped.
+pub(crate) struct Allocation {
+
+    pointer: *mut u8,
+    layout: Layout,
+
+    // The allocator is only used to free the memory, so it can't be observed in a broken state
+    // after a panic. Asserting this keeps Arrays unwind safe, just like Arrays backed by a Vec.
+    allocator: AssertUnwindSafe<Box<dyn GlobalAlloc + Send>>
+}
+
+impl Drop for Allocation {
+
+    fn drop(&mut self){
+        // Zero-sized layouts were never allocated, see try_create_garbage_in
+        if self.layout.size() != 0 {
+            unsafe {
+                self.allocator.dealloc(self.pointer, self.layout);
+            }
+        }
+    }
+}
+
+/// Forwards to the global allocator of the program (which is not necessarily the system allocator)
+struct Global;
+
+unsafe impl GlobalAlloc for Global {
+
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        alloc::alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout){
+        alloc::dealloc(pointer, layout);
+    }
+}
+
+impl<T> Array<T> {
+
+    /// Creates a new Array with the given size whose first element is aligned to the given alignment.
+    /// The memory will be claimed from the global allocator. The initial data will be 'garbage'.
+    /// This will panic if the size is 0 or if the alignment is not a power of 2 that is at least
+    /// the alignment of T.
+    pub fn create_garbage_aligned(size: usize, alignment: usize) -> Array<T> {
+        match Array::try_create_garbage_aligned(size, alignment) {
+            Ok(array) => array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Array with the given size whose first element is aligned to the given alignment,
+    /// just like create_garbage_aligned. Instead of panicking, this will return an error.
+    pub fn try_create_garbage_aligned(size: usize, alignment: usize) -> Result<Array<T>, MemoryStructuresError> {
+        Array::try_create_garbage_in(size, alignment, Global)
+    }
+
+    /// Creates a new Array with the given size whose memory will be claimed from the given allocator.
+    /// The first element will be aligned to the given alignment and the memory will be given back to
+    /// the allocator when the Array is dropped. The initial data will be 'garbage'.
+    /// This will panic if the size is 0, if the alignment is not a power of 2 that is at least
+    /// the alignment of T, or if the allocator fails.
+    pub fn create_garbage_in<A: GlobalAlloc + Send + 'static>(size: usize, alignment: usize, allocator: A) -> Array<T> {
+        match Array::try_create_garbage_in(size, alignment, allocator) {
+            Ok(array) => array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Array with the given size whose memory will be claimed from the given allocator,
+    /// just like create_garbage_in. Instead of panicking, this will return an error.
+    pub fn try_create_garbage_in<A: GlobalAlloc + Send + 'static>(size: usize, alignment: usize, allocator: A) -> Result<Array<T>, MemoryStructuresError> {
+        if size == 0 {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        let minimum_alignment = align_of::<T>();
+        if !alignment.is_power_of_two() || alignment < minimum_alignment {
+            return Err(MemoryStructuresError::InvalidAlignment { alignment, minimum_alignment });
+        }
+        let size_in_bytes = size.checked_mul(size_of::<T>()).ok_or(MemoryStructuresError::Overflow)?;
+        let layout = Layout::from_size_align(size_in_bytes, alignment).map_err(|_| MemoryStructuresError::Overflow)?;
+
+        let pointer = if size_in_bytes == 0 {
+            // Allocators must not be asked for 0 bytes, so zero-sized types just get an aligned dangling pointer
+            alignment as *mut u8
+        } else {
+            unsafe { allocator.alloc(layout) }
+        };
+        if pointer.is_null() {
+            return Err(MemoryStructuresError::AllocationFailed { size_in_bytes, alignment });
+        }
+
+        let allocation = Allocation {
+            pointer,
+            layout,
+            allocator: AssertUnwindSafe(Box::new(allocator))
+        };
+        Ok(unsafe { Array::from_owner(pointer as *mut T, size, MemoryOwner::Allocation(allocation)) })
+    }
+}
+
+impl<T: Copy> Array<T> {
+
+    /// Creates a new Array with the given size and alignment and sets all its elements to the given value.
+    /// This will panic if the size is 0 or if the alignment is invalid.
+    pub fn create_filled_aligned(size: usize, alignment: usize, value: T) -> Array<T> {
+        let array = Array::create_garbage_aligned(size, alignment);
+        array.set_all(value);
+        array
+    }
+
+    /// Creates a new Array with the given size and alignment and sets all its elements to the given value.
+    /// Instead of panicking, this method will return an error if the size is 0 or if the alignment is invalid.
+    pub fn try_create_filled_aligned(size: usize, alignment: usize, value: T) -> Result<Array<T>, MemoryStructuresError> {
+        let array = Array::try_create_garbage_aligned(size, alignment)?;
+        array.set_all(value);
+        Ok(array)
+    }
+}
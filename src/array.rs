@@ -1,7 +1,14 @@
+use crate::MemoryStructuresError;
+use crate::allocation::Allocation;
+use crate::simd;
+#[cfg(unix)]
+use crate::mapping::MemoryMapping;
+
 /// Some kind of array structure that uses interior mutability. It uses a Vec to claim
 /// a piece of memory that will then be used to store the contents of the array.
 /// The Vec will be kept as private field of the Array to make sure it won't be dropped
 /// before the Array is dropped and that it will be dropped as soon as the Array is dropped.
+/// On unix systems, an Array can also be backed by a memory-mapped file (see Array::map_file).
 /// 
 /// Many structs of this crate will be backed by an Array.
 /// 
@@ -20,17 +27,32 @@
 /// Even though sharing accross threads is unsafe, it was the main reason to create the
 /// Array struct. It is made for rare situations where performance is more important
 /// than correctness.
-
 pub struct Array<T> {
 
     size: usize,
     pointer: *mut T,
 
-    _memory_owner: Option<Vec<T>>
+    _memory_owner: Option<MemoryOwner<T>>
 }
 
 unsafe impl<T> Send for Array<T> {}
 
+/// The object that owns the memory of an Array that owns its own data. The memory will be
+/// released when the owner is dropped.
+pub(crate) enum MemoryOwner<T> {
+
+    // The Vec and Allocation are never read: they are only kept so that they will be dropped
+    // together with the Array
+    #[allow(dead_code)]
+    Vec(Vec<T>),
+
+    #[allow(dead_code)]
+    Allocation(Allocation),
+
+    #[cfg(unix)]
+    Mapping(MemoryMapping)
+}
+
 impl<T> Array<T> {
 
     /// Creates a new Array with the given size. If the size is 0, this method will panic.
@@ -42,25 +64,106 @@ impl<T> Array<T> {
         }
         let mut memory_owner = Vec::with_capacity(size);
         Array {
-            size: size,
+            size,
             pointer: memory_owner.as_mut_ptr(),
+            _memory_owner: Some(MemoryOwner::Vec(memory_owner))
+        }
+    }
+
+    /// Creates a new Array that takes ownership of the given Vec and uses its elements as initial data.
+    /// The length of the Vec will be the size of the Array. If the Vec is empty, this method will panic.
+    pub fn from_vec(vector: Vec<T>) -> Array<T> {
+        match Array::try_from_vec(vector) {
+            Ok(array) => array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Array that takes ownership of the given Vec, just like from_vec.
+    /// Instead of panicking, this method will return an error if the Vec is empty.
+    pub fn try_from_vec(mut vector: Vec<T>) -> Result<Array<T>, MemoryStructuresError> {
+        if vector.is_empty() {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        Ok(Array {
+            size: vector.len(),
+            pointer: vector.as_mut_ptr(),
+            _memory_owner: Some(MemoryOwner::Vec(vector))
+        })
+    }
+
+    /// Creates an Array that owns the memory of the given owner. The pointer must point to
+    /// memory owned by the owner that has room for at least size elements.
+    pub(crate) unsafe fn from_owner(pointer: *mut T, size: usize, memory_owner: MemoryOwner<T>) -> Array<T> {
+        Array {
+            size,
+            pointer,
             _memory_owner: Some(memory_owner)
         }
     }
 
+    /// Gets the owner of the memory of this Array, or None if this Array is a sharing copy.
+    pub(crate) fn get_memory_owner(&self) -> Option<&MemoryOwner<T>> {
+        self._memory_owner.as_ref()
+    }
+
+    /// Creates a new Array with the given size, just like create_garbage.
+    /// Instead of panicking, this method will return an error if the size is 0.
+    pub fn try_create_garbage(size: usize) -> Result<Array<T>, MemoryStructuresError> {
+        if size == 0 {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        Ok(Array::create_garbage(size))
+    }
+
     /// The size of this Array
     pub fn len(&self) -> usize {
         self.size
     }
 
+    /// Checks if this Array has a size of 0. Since Arrays of length 0 can't be
+    /// created, this will always return false.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
     /// Checks if the given index is smaller than the size of this Array.
     /// If so, this method will return silently. If not, it will panic.
     pub fn check_bound(&self, index: usize){
+        if let Err(error) = self.try_check_bound(index) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Checks if the given index is smaller than the size of this Array.
+    /// If so, this method will return Ok. If not, it will return an IndexOutOfBounds error.
+    pub fn try_check_bound(&self, index: usize) -> Result<(), MemoryStructuresError> {
         if index >= self.size {
-            panic!("Index is {} and size is {}", index, self.size);
+            return Err(MemoryStructuresError::IndexOutOfBounds { index, size: self.size });
+        }
+        Ok(())
+    }
+
+    /// Checks if the elements at indices start_index (inclusive) to start_index + amount (exclusive)
+    /// are all within the bounds of this Array. If not, this will panic.
+    pub fn check_range(&self, start_index: usize, amount: usize){
+        if let Err(error) = self.try_check_range(start_index, amount) {
+            panic!("{}", error);
         }
     }
 
+    /// Checks if the elements at indices start_index (inclusive) to start_index + amount (exclusive)
+    /// are all within the bounds of this Array. An empty range is always accepted.
+    pub fn try_check_range(&self, start_index: usize, amount: usize) -> Result<(), MemoryStructuresError> {
+        if amount != 0 {
+            let end_index = start_index.checked_add(amount - 1).ok_or(MemoryStructuresError::Overflow)?;
+            if end_index >= self.size {
+                return Err(MemoryStructuresError::RangeOutOfBounds { start_index, amount, size: self.size });
+            }
+        }
+        Ok(())
+    }
+
     /// Gets a reference to the element at the given index in this array.
     /// If the given index is not within the bounds of this array, this will panic.
     pub fn get_ref(&self, index: usize) -> &T {
@@ -76,15 +179,32 @@ impl<T> Array<T> {
         }
     }
 
+    /// Gets a reference to the element at the given index in this array.
+    /// If the given index is not within the bounds of this array, this will return an error.
+    pub fn try_get_ref(&self, index: usize) -> Result<&T, MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        Ok(self.get_unchecked_ref(index))
+    }
+
     /// Gets a mutable reference to the element at the given index in this array.
     /// If the given index is not within the bounds of this array, this will panic.
+    #[allow(clippy::mut_from_ref)]
     pub fn get_mut_ref(&self, index: usize) -> &mut T {
         self.check_bound(index);
         self.get_unchecked_mut_ref(index)
     }
 
+    /// Gets a mutable reference to the element at the given index in this array.
+    /// If the given index is not within the bounds of this array, this will return an error.
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_get_mut_ref(&self, index: usize) -> Result<&mut T, MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        Ok(self.get_unchecked_mut_ref(index))
+    }
+
     /// Gets a mutable reference to the element at the given index in this array.
     /// If the given index is not within the bounds of this array, undefined behavior will occur.
+    #[allow(clippy::mut_from_ref)]
     pub fn get_unchecked_mut_ref(&self, index: usize) -> &mut T {
         unsafe {
             self.pointer.add(index).as_mut().unwrap()
@@ -98,6 +218,14 @@ impl<T> Array<T> {
         self.set_unchecked(index, value);
     }
 
+    /// Sets the element at the specified index in this array to the given value.
+    /// If the given index is not within the bounds of this array, this will return an error.
+    pub fn try_set(&self, index: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        self.set_unchecked(index, value);
+        Ok(())
+    }
+
     /// Sets the element at the specified index in this array to the given value.
     /// If the given index is not within the bounds of this array, undefined behavior will occur.
     pub fn set_unchecked(&self, index: usize, value: T){
@@ -106,17 +234,51 @@ impl<T> Array<T> {
         }
     }
 
+    /// Gets a raw pointer to the first element of this Array. This can be used to pass the
+    /// contents of this Array to code that is not aware of this crate, like SIMD routines.
+    pub fn as_ptr(&self) -> *const T {
+        self.pointer
+    }
+
+    /// Gets a raw mutable pointer to the first element of this Array.
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.pointer
+    }
+
+    /// Checks if the address of the first element of this Array is a multiple of the given alignment.
+    pub fn is_aligned_to(&self, alignment: usize) -> bool {
+        alignment != 0 && (self.pointer as usize).is_multiple_of(alignment)
+    }
+
+    /// Gets a slice containing all elements of this Array. Note that the elements may still be
+    /// modified through this Array or its sharing copies while the slice exists.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        unsafe {
+            std::slice::from_raw_parts(self.pointer, self.size)
+        }
+    }
+
+    /// Gets a mutable slice containing all elements of this Array. Just like get_mut_ref,
+    /// this relies on the caller to avoid overlapping modifications.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn as_mut_slice(&self) -> &mut [T] {
+        unsafe {
+            std::slice::from_raw_parts_mut(self.pointer, self.size)
+        }
+    }
+
     /// Creates an Array instance that will share its data with this Array. This means
     /// that modifications to that Array will affect this Array and vice versa.
     /// This Array will keep owning its own data, but the returned Array will not have
     /// its own data but will use the data of this Array instead.
     /// 
+    /// # Safety
     /// This method is unsafe for 2 reasons:
     /// - If this Array gets dropped before the returned Array gets dropped, invoking methods
-    /// on the returned Array will manipulate data that is no longer owned and will lead to
-    /// undefined behavior.
+    ///   on the returned Array will manipulate data that is no longer owned and will lead to
+    ///   undefined behavior.
     /// - The returned Array can be sent to another thread and cause (small) concurrency problems
-    /// since this struct doesn't provide any atomic mechanism.
+    ///   since this struct doesn't provide any atomic mechanism.
     pub unsafe fn sharing_copy(&self) -> Array<T> {
         Array {
             size: self.size,
@@ -125,16 +287,35 @@ impl<T> Array<T> {
         }
     }
 
+    /// Creates an Array instance that will share the elements at indices start_index (inclusive)
+    /// to start_index + size (exclusive) with this Array. This will panic if the size is 0 or if
+    /// the range is not within the bounds of this Array.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as sharing_copy.
     pub unsafe fn sharing_sub_array(&self, start_index: usize, size: usize) -> Array<T> {
+        match self.try_sharing_sub_array(start_index, size) {
+            Ok(sub_array) => sub_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates an Array instance that will share the elements at indices start_index (inclusive)
+    /// to start_index + size (exclusive) with this Array. Instead of panicking, this will return
+    /// an error if the size is 0 or if the range is not within the bounds of this Array.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as sharing_copy.
+    pub unsafe fn try_sharing_sub_array(&self, start_index: usize, size: usize) -> Result<Array<T>, MemoryStructuresError> {
         if size == 0 {
-            panic!("Size must not be 0");
+            return Err(MemoryStructuresError::ZeroSize);
         }
-        self.check_bound(start_index.checked_add(size - 1).unwrap());
-        Array {
-            size: size,
+        self.try_check_range(start_index, size)?;
+        Ok(Array {
+            size,
             pointer: self.pointer.add(start_index),
             _memory_owner: None
-        }
+        })
     }
 }
 
@@ -157,6 +338,67 @@ impl<T> IndexMut<usize> for Array<T> {
     }
 }
 
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+
+/// The maximum number of elements that the Debug implementations of Array and Table will show
+/// per list. The remaining elements will be summarized.
+pub(crate) const DEBUG_ELEMENT_LIMIT: usize = 32;
+
+/// Formats the first elements of a list with the given total length as a Debug list that shows at
+/// most DEBUG_ELEMENT_LIMIT elements.
+pub(crate) struct TruncatedList<'a, T>(pub(crate) &'a [T], pub(crate) usize);
+
+impl<'a, T: Debug> Debug for TruncatedList<'a, T> {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(DEBUG_ELEMENT_LIMIT));
+        if self.1 > DEBUG_ELEMENT_LIMIT {
+            list.entry(&format_args!("... {} more", self.1 - DEBUG_ELEMENT_LIMIT));
+        }
+        list.finish()
+    }
+}
+
+impl<T: Debug> Debug for Array<T> {
+
+    /// Shows the length and the elements of this Array. Only the first 32 elements will be shown.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Array")
+            .field("len", &self.size)
+            .field("elements", &TruncatedList(self.as_slice(), self.size))
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for Array<T> {
+
+    /// Creates a new Array that owns a copy of the elements of this Array. Unlike sharing_copy,
+    /// modifications to the clone will not affect this Array. Cloning a sharing copy will
+    /// thus also create an Array that owns its data.
+    fn clone(&self) -> Array<T> {
+        Array::from_vec(self.as_slice().to_vec())
+    }
+}
+
+impl<T: PartialEq> PartialEq for Array<T> {
+
+    /// Two Arrays are equal when they have the same length and equal elements.
+    fn eq(&self, other: &Array<T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for Array<T> {}
+
+impl<T: Hash> Hash for Array<T> {
+
+    fn hash<H: Hasher>(&self, state: &mut H){
+        self.as_slice().hash(state);
+    }
+}
+
 impl<T: Copy> Array<T> {
 
     pub fn create_filled(size: usize, value: T) -> Array<T> {
@@ -165,19 +407,31 @@ impl<T: Copy> Array<T> {
         array
     }
 
+    /// Creates a new Array with the given size and sets all its elements to the given value.
+    /// Instead of panicking, this method will return an error if the size is 0.
+    pub fn try_create_filled(size: usize, value: T) -> Result<Array<T>, MemoryStructuresError> {
+        let array = Array::try_create_garbage(size)?;
+        array.set_all(value);
+        Ok(array)
+    }
+
     pub fn create_vector(&self, start_index: usize, length: usize) -> Vec<T> {
-        if start_index + length > self.size {
-            panic!("start_index is {} and length is {}, but the size of this array is {}", start_index, length, self.size);
+        match self.try_create_vector(start_index, length) {
+            Ok(vector) => vector,
+            Err(error) => panic!("{}", error)
         }
+    }
 
-        let mut vector = Vec::with_capacity(length);
-        // TODO maybe improve performance of this
-        let mut index = start_index;
-        for _ in 0..length {
-            vector.push(self.get_unchecked(index));
-            index += 1;
+    /// Creates a Vec containing copies of the elements at indices start_index (inclusive) to
+    /// start_index + length (exclusive). If that range is out of bounds, an error will be returned.
+    pub fn try_create_vector(&self, start_index: usize, length: usize) -> Result<Vec<T>, MemoryStructuresError> {
+        self.try_check_range(start_index, length)?;
+
+        // An empty range is accepted for any start_index, which may be out of bounds for the slice
+        if length == 0 {
+            return Ok(Vec::new());
         }
-        vector
+        Ok(self.as_slice()[start_index..start_index + length].to_vec())
     }
 
     /// Sets some elements of this Array to (copies of) the specified value.
@@ -188,18 +442,34 @@ impl<T: Copy> Array<T> {
             let end_index = start_index.checked_add(amount - 1).unwrap();
             self.check_bound(end_index);
             unsafe {
-                for index in start_index..=end_index {
-                    *self.pointer.add(index) = value;
+                let start = self.pointer.add(start_index);
+                if !simd::try_fill(start, amount, value) {
+                    for index in 0..amount {
+                        start.add(index).write(value);
+                    }
                 }
             }
         }
     }
 
-    /// Sets all elements in this Array to (a copy of) the specified value.
+    /// Sets some elements of this Array to (copies of) the specified value, just like set_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_set_some(&self, start_index: usize, amount: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount)?;
+        self.set_some(start_index, amount, value);
+        Ok(())
+    }
+
+    /// Sets all elements in this Array to (a copy of) the specified value. This uses vector
+    /// instructions for the primitive integer and float types.
     pub fn set_all(&self, value: T){
+
+        // The elements may not be initialized yet (create_filled uses this), so don't create a slice
         unsafe {
-            for index in 0..self.size {
-                *self.pointer.add(index) = value;
+            if !simd::try_fill(self.pointer, self.size, value) {
+                for index in 0..self.size {
+                    self.pointer.add(index).write(value);
+                }
             }
         }
     }
@@ -211,6 +481,13 @@ impl<T: Copy> Array<T> {
         self.get_unchecked(index)
     }
 
+    /// Gets and returns a copy of the element at the specified index in this Array.
+    /// If the index is outside the array bounds, this will return an error.
+    pub fn try_get(&self, index: usize) -> Result<T, MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        Ok(self.get_unchecked(index))
+    }
+
     /// Gets and returns a copy of the element at the specified index in this Array.
     /// If the index is outside the bounds of this Array, undefined behavior occurs.
     pub fn get_unchecked(&self, index: usize) -> T {
@@ -240,6 +517,14 @@ impl<T: AddAssign + Copy> Array<T> {
         self.add_unchecked(index, amount);
     }
 
+    /// Increases that element at the given index in this array by the specified amount.
+    /// If the index is not within the bounds, this method will return an error
+    pub fn try_add(&self, index: usize, amount: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        self.add_unchecked(index, amount);
+        Ok(())
+    }
+
     /// Increases some elements of this Array by the specified amount.
     /// The elements at indices start_index (inclusive) to start_index + amount (exclusive)
     /// will be increased by the specified amount.
@@ -247,11 +532,19 @@ impl<T: AddAssign + Copy> Array<T> {
     /// Undefined behavior occurs if start_index + amount_of_elements > len()
     pub fn add_unchecked_some(&self, start_index: usize, amount_of_elements: usize, amount_to_add: T){
         if amount_of_elements != 0 {
-            let end_index = start_index.checked_add(amount_of_elements - 1).unwrap();
+            start_index.checked_add(amount_of_elements - 1).unwrap();
             unsafe {
-                for index in start_index..=end_index {
-                    *self.pointer.add(index) += amount_to_add;
-                }
+                Array::add_to_elements(self.pointer.add(start_index), amount_of_elements, amount_to_add);
+            }
+        }
+    }
+
+    /// Increases the amount_of_elements elements starting at start by amount_to_add, using vector
+    /// instructions for the primitive float types, and for the primitive integer types in release builds.
+    unsafe fn add_to_elements(start: *mut T, amount_of_elements: usize, amount_to_add: T){
+        if !simd::try_add(start, amount_of_elements, amount_to_add) {
+            for index in 0..amount_of_elements {
+                *start.add(index) += amount_to_add;
             }
         }
     }
@@ -266,19 +559,24 @@ impl<T: AddAssign + Copy> Array<T> {
             let end_index = start_index.checked_add(amount_of_elements - 1).unwrap();
             self.check_bound(end_index);
             unsafe {
-                for index in start_index..=end_index {
-                    *self.pointer.add(index) += amount_to_add;
-                }
+                Array::add_to_elements(self.pointer.add(start_index), amount_of_elements, amount_to_add);
             }
         }
     }
 
-    /// Increases all elements in this Array by the specified amount.
+    /// Increases some elements of this Array by the specified amount, just like add_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_add_some(&self, start_index: usize, amount_of_elements: usize, amount_to_add: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        self.add_unchecked_some(start_index, amount_of_elements, amount_to_add);
+        Ok(())
+    }
+
+    /// Increases all elements in this Array by the specified amount. This uses vector instructions
+    /// for the primitive integer and float types (see the simd module).
     pub fn add_all(&self, amount: T){
         unsafe {
-            for index in 0..self.size {
-                *self.pointer.add(index) += amount;
-            }
+            Array::add_to_elements(self.pointer, self.size, amount);
         }
     }
 }
@@ -296,6 +594,14 @@ impl<T: Saturating + Copy> Array<T> {
         }
     }
 
+    /// Performs a saturating add on the element at the given index in this Array by the given amount.
+    /// If the index is not within the bounds, this method will return an error.
+    pub fn try_saturating_add(&self, index: usize, amount: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        self.saturating_add(index, amount);
+        Ok(())
+    }
+
     /// Performs saturating add on some elements of this Array by the specified value.
     /// The elements at indices start_index (inclusive) to start_index + amount (exclusive)
     /// will be increased.
@@ -304,20 +610,34 @@ impl<T: Saturating + Copy> Array<T> {
             let end_index = start_index.checked_add(amount_of_elements - 1).unwrap();
             self.check_bound(end_index);
             unsafe {
-                for index in start_index..=end_index {
-                    let location = self.pointer.add(index);
-                    *location = (*location).saturating_add(amount_to_add);
-                }
+                Array::saturating_add_to_elements(self.pointer.add(start_index), amount_of_elements, amount_to_add);
             }
         }
     }
 
-    /// Performs a saturating addition on all elements in this Array by the given amount.
+    /// Performs saturating add on some elements of this Array, just like saturating_add_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_saturating_add_some(&self, start_index: usize, amount_of_elements: usize, amount_to_add: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        self.saturating_add_some(start_index, amount_of_elements, amount_to_add);
+        Ok(())
+    }
+
+    /// Performs a saturating addition on all elements in this Array by the given amount. This uses
+    /// vector instructions for the 8-bit and 16-bit integer types.
     pub fn saturating_add_all(&self, amount: T){
         unsafe {
-            for index in 0..self.size {
-                let location = self.pointer.add(index);
-                *location = (*location).saturating_add(amount);
+            Array::saturating_add_to_elements(self.pointer, self.size, amount);
+        }
+    }
+
+    /// Performs a saturating addition of amount_to_add on the amount_of_elements elements starting at
+    /// start, using vector instructions for the 8-bit and 16-bit integer types.
+    unsafe fn saturating_add_to_elements(start: *mut T, amount_of_elements: usize, amount_to_add: T){
+        if !simd::try_saturating_add(start, amount_of_elements, amount_to_add) {
+            for index in 0..amount_of_elements {
+                let location = start.add(index);
+                *location = (*location).saturating_add(amount_to_add);
             }
         }
     }
@@ -0,0 +1,581 @@
+use crate::Array;
+use crate::Graphics2D;
+use crate::MemoryStructuresError;
+use crate::summed_area::check_rect;
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+const WORD_BITS: usize = 64;
+
+/// Calls consumer with the index and mask of every word that contains some of the bits at indices
+/// start_index (inclusive) to start_index + amount (exclusive). The mask has a 1 for every such bit.
+fn for_each_word_mask<F: FnMut(usize, u64)>(start_index: usize, amount: usize, mut consumer: F){
+    if amount == 0 {
+        return;
+    }
+    let end_index = start_index + amount;
+    let first_word = start_index / WORD_BITS;
+    let last_word = (end_index - 1) / WORD_BITS;
+    for word_index in first_word..=last_word {
+        let low = if word_index == first_word { start_index % WORD_BITS } else { 0 };
+        let high = if word_index == last_word { (end_index - 1) % WORD_BITS + 1 } else { WORD_BITS };
+        let mask = if high - low == WORD_BITS { !0 } else { ((1u64 << (high - low)) - 1) << low };
+        consumer(word_index, mask);
+    }
+}
+
+/// An array of booleans that packs 64 elements in every u64 word, so it uses 8 times less memory than
+/// an Array<bool>. Just like Array, all modifying methods only need a shared reference. The bits after
+/// the last element of the last word are always 0, so they never influence count_ones or comparisons.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitArray {
+
+    words: Array<u64>,
+    size: usize
+}
+
+impl BitArray {
+
+    /// Creates a new BitArray with the given size and sets all its elements to the given value.
+    /// This will panic if the size is 0.
+    pub fn create_filled(size: usize, value: bool) -> BitArray {
+        match BitArray::try_create_filled(size, value) {
+            Ok(bits) => bits,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new BitArray, just like create_filled. Instead of panicking, this will return an error.
+    pub fn try_create_filled(size: usize, value: bool) -> Result<BitArray, MemoryStructuresError> {
+        if size == 0 {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        let bits = BitArray { words: Array::try_create_filled(size.div_ceil(WORD_BITS), 0)?, size };
+        bits.set_all(value);
+        Ok(bits)
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Always returns false, since a BitArray can't be empty
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// The words that store the elements: the element at index i is bit i % 64 of word i / 64.
+    /// The bits after the last element are always 0.
+    pub fn get_words(&self) -> &[u64] {
+        self.words.as_slice()
+    }
+
+    /// Checks if the given index is smaller than the size of this BitArray. If not, this will return an
+    /// IndexOutOfBounds error.
+    pub fn try_check_bound(&self, index: usize) -> Result<(), MemoryStructuresError> {
+        if index >= self.size {
+            return Err(MemoryStructuresError::IndexOutOfBounds { index, size: self.size });
+        }
+        Ok(())
+    }
+
+    /// Checks if the elements at indices start_index (inclusive) to start_index + amount (exclusive)
+    /// are all within the bounds of this BitArray. An empty range is always accepted.
+    pub fn try_check_range(&self, start_index: usize, amount: usize) -> Result<(), MemoryStructuresError> {
+        if amount != 0 {
+            let end_index = start_index.checked_add(amount - 1).ok_or(MemoryStructuresError::Overflow)?;
+            if end_index >= self.size {
+                return Err(MemoryStructuresError::RangeOutOfBounds { start_index, amount, size: self.size });
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the element at the given index. This will panic if the index is out of bounds.
+    pub fn get(&self, index: usize) -> bool {
+        match self.try_get(index) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the element at the given index. This will return an error if the index is out of bounds.
+    pub fn try_get(&self, index: usize) -> Result<bool, MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        Ok(self.get_unchecked(index))
+    }
+
+    /// Gets the element at the given index. If the index is out of bounds, undefined behavior will occur.
+    pub fn get_unchecked(&self, index: usize) -> bool {
+        (self.words.get_unchecked(index / WORD_BITS) >> (index % WORD_BITS)) & 1 == 1
+    }
+
+    /// Sets the element at the given index. This will panic if the index is out of bounds.
+    pub fn set(&self, index: usize, value: bool){
+        if let Err(error) = self.try_set(index, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets the element at the given index. This will return an error if the index is out of bounds.
+    pub fn try_set(&self, index: usize, value: bool) -> Result<(), MemoryStructuresError> {
+        self.try_check_bound(index)?;
+        self.set_unchecked(index, value);
+        Ok(())
+    }
+
+    /// Sets the element at the given index. If the index is out of bounds, undefined behavior will occur.
+    pub fn set_unchecked(&self, index: usize, value: bool){
+        let word = self.words.get_unchecked_mut_ref(index / WORD_BITS);
+        let mask = 1u64 << (index % WORD_BITS);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Sets the elements at indices start_index (inclusive) to start_index + amount (exclusive) to the
+    /// given value, a whole word at a time. This will panic if the range is out of bounds.
+    pub fn set_some(&self, start_index: usize, amount: usize, value: bool){
+        if let Err(error) = self.try_set_some(start_index, amount, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets some elements to the given value, just like set_some. Instead of panicking, this will return
+    /// an error if the range is out of bounds.
+    pub fn try_set_some(&self, start_index: usize, amount: usize, value: bool) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount)?;
+        self.set_unchecked_some(start_index, amount, value);
+        Ok(())
+    }
+
+    /// Sets some elements to the given value, just like set_some. If the range is out of bounds,
+    /// undefined behavior will occur.
+    pub fn set_unchecked_some(&self, start_index: usize, amount: usize, value: bool){
+        for_each_word_mask(start_index, amount, |word_index, mask| {
+            let word = self.words.get_unchecked_mut_ref(word_index);
+            if value {
+                *word |= mask;
+            } else {
+                *word &= !mask;
+            }
+        });
+    }
+
+    /// Sets all elements of this BitArray to the given value
+    pub fn set_all(&self, value: bool){
+        self.words.set_all(if value { !0 } else { 0 });
+        self.clear_unused_bits();
+    }
+
+    /// Clears the bits of the last word that don't belong to any element
+    fn clear_unused_bits(&self){
+        let used_bits = self.size % WORD_BITS;
+        if used_bits != 0 {
+            *self.words.get_unchecked_mut_ref(self.words.len() - 1) &= (1u64 << used_bits) - 1;
+        }
+    }
+
+    /// Counts the number of elements that are true
+    pub fn count_ones(&self) -> usize {
+        self.words.as_slice().iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Counts the number of true elements at indices start_index (inclusive) to start_index + amount
+    /// (exclusive). This will panic if the range is out of bounds.
+    pub fn count_ones_some(&self, start_index: usize, amount: usize) -> usize {
+        match self.try_count_ones_some(start_index, amount) {
+            Ok(count) => count,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Counts the number of true elements in a range, just like count_ones_some. Instead of panicking,
+    /// this will return an error if the range is out of bounds.
+    pub fn try_count_ones_some(&self, start_index: usize, amount: usize) -> Result<usize, MemoryStructuresError> {
+        self.try_check_range(start_index, amount)?;
+        Ok(self.count_unchecked_ones_some(start_index, amount))
+    }
+
+    pub(crate) fn count_unchecked_ones_some(&self, start_index: usize, amount: usize) -> usize {
+        let mut count = 0;
+        for_each_word_mask(start_index, amount, |word_index, mask| {
+            count += (self.words.get_unchecked(word_index) & mask).count_ones() as usize;
+        });
+        count
+    }
+
+    fn try_combine<F: Fn(u64, u64) -> u64>(&self, other: &BitArray, combine: F) -> Result<(), MemoryStructuresError> {
+        if self.size != other.size {
+            return Err(MemoryStructuresError::LengthMismatch { expected_length: self.size, length: other.size });
+        }
+        // The words are copied one by one because other can share its words with this BitArray (for
+        // instance in bits.and(&bits)), so no slices of both can exist at the same time
+        for index in 0..self.words.len() {
+            self.words.set_unchecked(index, combine(self.words.get_unchecked(index), other.words.get_unchecked(index)));
+        }
+        Ok(())
+    }
+
+    /// Sets every element of this BitArray to the logical AND of itself and the element of other at the
+    /// same index. This will panic if other has a different length.
+    pub fn and(&self, other: &BitArray){
+        if let Err(error) = self.try_and(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this BitArray with other, just like and. Instead of panicking, this will return an error.
+    pub fn try_and(&self, other: &BitArray) -> Result<(), MemoryStructuresError> {
+        self.try_combine(other, |word, other_word| word & other_word)
+    }
+
+    /// Sets every element of this BitArray to the logical OR of itself and the element of other at the
+    /// same index. This will panic if other has a different length.
+    pub fn or(&self, other: &BitArray){
+        if let Err(error) = self.try_or(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this BitArray with other, just like or. Instead of panicking, this will return an error.
+    pub fn try_or(&self, other: &BitArray) -> Result<(), MemoryStructuresError> {
+        self.try_combine(other, |word, other_word| word | other_word)
+    }
+
+    /// Sets every element of this BitArray to the logical XOR of itself and the element of other at the
+    /// same index. This will panic if other has a different length.
+    pub fn xor(&self, other: &BitArray){
+        if let Err(error) = self.try_xor(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this BitArray with other, just like xor. Instead of panicking, this will return an error.
+    pub fn try_xor(&self, other: &BitArray) -> Result<(), MemoryStructuresError> {
+        self.try_combine(other, |word, other_word| word ^ other_word)
+    }
+
+    /// Inverts every element of this BitArray
+    pub fn invert(&self){
+        for word in self.words.as_mut_slice().iter_mut() {
+            *word = !*word;
+        }
+        self.clear_unused_bits();
+    }
+}
+
+impl BitAnd for &BitArray {
+    type Output = BitArray;
+
+    /// Creates a new BitArray that is the logical AND of both operands. This will panic if their
+    /// lengths are different.
+    fn bitand(self, other: &BitArray) -> BitArray {
+        let result = self.clone();
+        result.and(other);
+        result
+    }
+}
+
+impl BitOr for &BitArray {
+    type Output = BitArray;
+
+    /// Creates a new BitArray that is the logical OR of both operands. This will panic if their
+    /// lengths are different.
+    fn bitor(self, other: &BitArray) -> BitArray {
+        let result = self.clone();
+        result.or(other);
+        result
+    }
+}
+
+impl BitXor for &BitArray {
+    type Output = BitArray;
+
+    /// Creates a new BitArray that is the logical XOR of both operands. This will panic if their
+    /// lengths are different.
+    fn bitxor(self, other: &BitArray) -> BitArray {
+        let result = self.clone();
+        result.xor(other);
+        result
+    }
+}
+
+impl Not for &BitArray {
+    type Output = BitArray;
+
+    fn not(self) -> BitArray {
+        let result = self.clone();
+        result.invert();
+        result
+    }
+}
+
+/// A 2-dimensional table of booleans that is backed by a BitArray, so every element uses only 1 bit.
+/// The element at (x, y) is stored at index x + y * width of the BitArray. Since BitTable implements
+/// Graphics2D, lines and rectangles can be drawn into it. Drawing with true sets the elements and
+/// drawing with false clears them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitTable {
+
+    bits: BitArray,
+
+    width: usize,
+    height: usize
+}
+
+impl BitTable {
+
+    /// Creates a new BitTable with the given width and height and sets all its elements to the given
+    /// value. This will panic if the width or height is 0.
+    pub fn create_filled(width: usize, height: usize, value: bool) -> BitTable {
+        match BitTable::try_create_filled(width, height, value) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new BitTable, just like create_filled. Instead of panicking, this will return an error.
+    pub fn try_create_filled(width: usize, height: usize, value: bool) -> Result<BitTable, MemoryStructuresError> {
+        if width == 0 || height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
+        }
+        let size = width.checked_mul(height).ok_or(MemoryStructuresError::Overflow)?;
+        Ok(BitTable { bits: BitArray::try_create_filled(size, value)?, width, height })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// The BitArray that stores the elements of this table, row by row
+    pub fn get_bits(&self) -> &BitArray {
+        &self.bits
+    }
+
+    /// Gets the index in the BitArray of the element at (x, y). This will return an error if x or y is
+    /// outside this table.
+    pub fn try_index_for(&self, x: usize, y: usize) -> Result<usize, MemoryStructuresError> {
+        if x >= self.width || y >= self.height {
+            return Err(MemoryStructuresError::CoordinatesOutOfBounds { x, y, width: self.width, height: self.height });
+        }
+        Ok(x + y * self.width)
+    }
+
+    /// Gets the element at (x, y). This will panic if x or y is outside this table.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        match self.try_get(x, y) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the element at (x, y). This will return an error if x or y is outside this table.
+    pub fn try_get(&self, x: usize, y: usize) -> Result<bool, MemoryStructuresError> {
+        Ok(self.bits.get_unchecked(self.try_index_for(x, y)?))
+    }
+
+    /// Gets the element at (x, y). If x or y is outside this table, the result is unspecified and
+    /// undefined behavior can occur.
+    pub fn get_unchecked(&self, x: usize, y: usize) -> bool {
+        self.bits.get_unchecked(x + y * self.width)
+    }
+
+    /// Sets the element at (x, y). This will panic if x or y is outside this table.
+    pub fn set(&self, x: usize, y: usize, value: bool){
+        if let Err(error) = self.try_set(x, y, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets the element at (x, y). This will return an error if x or y is outside this table.
+    pub fn try_set(&self, x: usize, y: usize, value: bool) -> Result<(), MemoryStructuresError> {
+        self.bits.set_unchecked(self.try_index_for(x, y)?, value);
+        Ok(())
+    }
+
+    /// Sets the element at (x, y). If x or y is outside this table, undefined behavior can occur.
+    pub fn set_unchecked(&self, x: usize, y: usize, value: bool){
+        self.bits.set_unchecked(x + y * self.width, value);
+    }
+
+    /// Sets all elements in the rectangle from (min_x, min_y) to (max_x, max_y) (inclusive) to the given
+    /// value. This will panic if the rectangle is empty or doesn't fit in this table.
+    pub fn set_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, value: bool){
+        if let Err(error) = self.try_set_rect(min_x, min_y, max_x, max_y, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets all elements in a rectangle, just like set_rect. Instead of panicking, this will return an error.
+    pub fn try_set_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, value: bool) -> Result<(), MemoryStructuresError> {
+        check_rect(min_x, min_y, max_x, max_y, self.width, self.height)?;
+        self.set_unchecked_rect(min_x, min_y, max_x, max_y, value);
+        Ok(())
+    }
+
+    /// Sets all elements in a rectangle, just like set_rect. If the rectangle is empty or doesn't fit in
+    /// this table, undefined behavior can occur.
+    pub fn set_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, value: bool){
+        if min_x == 0 && max_x == self.width - 1 {
+            self.bits.set_unchecked_some(min_y * self.width, (max_y - min_y + 1) * self.width, value);
+        } else {
+            for y in min_y..=max_y {
+                self.bits.set_unchecked_some(min_x + y * self.width, max_x - min_x + 1, value);
+            }
+        }
+    }
+
+    /// Sets all elements of this table to the given value
+    pub fn set_all(&self, value: bool){
+        self.bits.set_all(value);
+    }
+
+    /// Counts the number of elements that are true
+    pub fn count_ones(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    /// Counts the number of true elements in the rectangle from (min_x, min_y) to (max_x, max_y)
+    /// (inclusive). This will panic if the rectangle is empty or doesn't fit in this table.
+    pub fn count_ones_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> usize {
+        match self.try_count_ones_rect(min_x, min_y, max_x, max_y) {
+            Ok(count) => count,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Counts the number of true elements in a rectangle, just like count_ones_rect. Instead of panicking,
+    /// this will return an error.
+    pub fn try_count_ones_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> Result<usize, MemoryStructuresError> {
+        check_rect(min_x, min_y, max_x, max_y, self.width, self.height)?;
+        Ok((min_y..=max_y).map(|y| self.bits.count_unchecked_ones_some(min_x + y * self.width, max_x - min_x + 1)).sum())
+    }
+
+    fn try_check_dimensions(&self, other: &BitTable) -> Result<(), MemoryStructuresError> {
+        if self.width != other.width || self.height != other.height {
+            return Err(MemoryStructuresError::DimensionMismatch {
+                expected_width: self.width, expected_height: self.height, width: other.width, height: other.height
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets every element of this table to the logical AND of itself and the element of other at the same
+    /// position. This will panic if other has a different width or height.
+    pub fn and(&self, other: &BitTable){
+        if let Err(error) = self.try_and(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this table with other, just like and. Instead of panicking, this will return an error.
+    pub fn try_and(&self, other: &BitTable) -> Result<(), MemoryStructuresError> {
+        self.try_check_dimensions(other)?;
+        self.bits.try_and(&other.bits)
+    }
+
+    /// Sets every element of this table to the logical OR of itself and the element of other at the same
+    /// position. This will panic if other has a different width or height.
+    pub fn or(&self, other: &BitTable){
+        if let Err(error) = self.try_or(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this table with other, just like or. Instead of panicking, this will return an error.
+    pub fn try_or(&self, other: &BitTable) -> Result<(), MemoryStructuresError> {
+        self.try_check_dimensions(other)?;
+        self.bits.try_or(&other.bits)
+    }
+
+    /// Sets every element of this table to the logical XOR of itself and the element of other at the same
+    /// position. This will panic if other has a different width or height.
+    pub fn xor(&self, other: &BitTable){
+        if let Err(error) = self.try_xor(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Combines this table with other, just like xor. Instead of panicking, this will return an error.
+    pub fn try_xor(&self, other: &BitTable) -> Result<(), MemoryStructuresError> {
+        self.try_check_dimensions(other)?;
+        self.bits.try_xor(&other.bits)
+    }
+
+    /// Inverts every element of this table
+    pub fn invert(&self){
+        self.bits.invert();
+    }
+}
+
+impl BitAnd for &BitTable {
+    type Output = BitTable;
+
+    /// Creates a new BitTable that is the logical AND of both operands. This will panic if their
+    /// dimensions are different.
+    fn bitand(self, other: &BitTable) -> BitTable {
+        let result = self.clone();
+        result.and(other);
+        result
+    }
+}
+
+impl BitOr for &BitTable {
+    type Output = BitTable;
+
+    /// Creates a new BitTable that is the logical OR of both operands. This will panic if their
+    /// dimensions are different.
+    fn bitor(self, other: &BitTable) -> BitTable {
+        let result = self.clone();
+        result.or(other);
+        result
+    }
+}
+
+impl BitXor for &BitTable {
+    type Output = BitTable;
+
+    /// Creates a new BitTable that is the logical XOR of both operands. This will panic if their
+    /// dimensions are different.
+    fn bitxor(self, other: &BitTable) -> BitTable {
+        let result = self.clone();
+        result.xor(other);
+        result
+    }
+}
+
+impl Not for &BitTable {
+    type Output = BitTable;
+
+    fn not(self) -> BitTable {
+        let result = self.clone();
+        result.invert();
+        result
+    }
+}
+
+impl Graphics2D<bool> for BitTable {
+
+    fn add_unchecked(&self, x: usize, y: usize, amount: bool){
+        self.set_unchecked(x, y, amount);
+    }
+
+    fn add_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, amount: bool){
+        self.set_unchecked_rect(min_x, min_y, max_x, max_y, amount);
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+}
@@ -0,0 +1,160 @@
+use crate::Array;
+use crate::Table;
+use crate::utility::ToF64;
+
+use std::convert::TryFrom;
+
+/// Determines which elements are neighbors when labeling connected components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+
+    /// Only the elements directly left, right, above and below are neighbors.
+    Four,
+
+    /// The 4 diagonal elements are also neighbors.
+    Eight
+}
+
+/// The statistics of a single connected component, as computed by Table::label_components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentStats {
+
+    /// The label of the component in the label table, which is at least 1
+    pub label: u32,
+
+    /// The number of elements in the component
+    pub area: usize,
+
+    pub min_x: usize,
+    pub min_y: usize,
+    pub max_x: usize,
+    pub max_y: usize,
+
+    /// The average x-coordinate of the elements in the component
+    pub centroid_x: f64,
+
+    /// The average y-coordinate of the elements in the component
+    pub centroid_y: f64,
+
+    /// The sum of the values of the elements in the component
+    pub sum: f64
+}
+
+/// Finds the root of the set that contains the given label, and shortens the path to it
+fn find_root(parents: &mut [u32], label: u32) -> u32 {
+    let mut root = label;
+    while parents[root as usize] != root {
+        root = parents[root as usize];
+    }
+    let mut current = label;
+    while parents[current as usize] != root {
+        let next = parents[current as usize];
+        parents[current as usize] = root;
+        current = next;
+    }
+    root
+}
+
+/// Merges the sets of both labels. The smallest root becomes the root of the merged set.
+fn union(parents: &mut [u32], label1: u32, label2: u32) -> u32 {
+    let root1 = find_root(parents, label1);
+    let root2 = find_root(parents, label2);
+    let (root, child) = if root1 < root2 { (root1, root2) } else { (root2, root1) };
+    parents[child as usize] = root;
+    root
+}
+
+impl<T: ToF64 + Default + PartialEq + Copy> Table<T> {
+
+    /// Labels the connected components of the elements that are not 0 (the default value of T), just like
+    /// label_components_by.
+    pub fn label_components(&self, connectivity: Connectivity) -> (Table<u32>, Vec<ComponentStats>) {
+        let zero = T::default();
+        self.label_components_by(connectivity, |element| element != zero)
+    }
+
+    /// Labels the connected components of the foreground elements, which are the elements for which
+    /// is_foreground returns true. This returns a table with the same width and height in which every
+    /// background element is 0 and every foreground element is the label of its component, together
+    /// with the statistics of every component. The components are labeled 1, 2, 3, ... in the order in
+    /// which they are first encountered when scanning row by row, and the statistics of the component
+    /// with label l are at index l - 1. This uses the two-pass algorithm with union-find. This will panic
+    /// if the first pass needs more labels than fit in a u32.
+    pub fn label_components_by<F: Fn(T) -> bool>(&self, connectivity: Connectivity, is_foreground: F) -> (Table<u32>, Vec<ComponentStats>) {
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut labels = vec![0u32; width * height];
+
+        // Label 0 is the background, so the parent of every provisional label is stored at that label
+        let mut parents = vec![0u32];
+
+        // The first pass gives every foreground element the smallest label of its previous neighbors
+        let mut previous_neighbors = vec![(-1, 0), (0, -1)];
+        if connectivity == Connectivity::Eight {
+            previous_neighbors.push((-1, -1));
+            previous_neighbors.push((1, -1));
+        }
+        for y in 0..height {
+            for x in 0..width {
+                if !is_foreground(self.get_unchecked(x, y)) {
+                    continue;
+                }
+                let mut label = 0;
+                for (dx, dy) in previous_neighbors.iter() {
+                    let (nx, ny) = (x as isize + dx, y as isize + dy);
+                    if nx < 0 || nx as usize >= width || ny < 0 {
+                        continue;
+                    }
+                    let neighbor_label = labels[nx as usize + ny as usize * width];
+                    if neighbor_label != 0 {
+                        label = if label == 0 { neighbor_label } else { union(&mut parents, label, neighbor_label) };
+                    }
+                }
+                if label == 0 {
+                    label = match u32::try_from(parents.len()) {
+                        Ok(label) => label,
+                        Err(_) => panic!("The table has more than {} provisional components, which can't be labeled with u32", u32::MAX)
+                    };
+                    parents.push(label);
+                }
+                labels[x + y * width] = label;
+            }
+        }
+
+        // The second pass replaces every provisional label with the final label of its set
+        let mut final_labels = vec![0u32; parents.len()];
+        let mut components: Vec<ComponentStats> = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let provisional_label = labels[x + y * width];
+                if provisional_label == 0 {
+                    continue;
+                }
+                let root = find_root(&mut parents, provisional_label) as usize;
+                if final_labels[root] == 0 {
+                    components.push(ComponentStats {
+                        label: components.len() as u32 + 1,
+                        area: 0, min_x: x, min_y: y, max_x: x, max_y: y,
+                        centroid_x: 0.0, centroid_y: 0.0, sum: 0.0
+                    });
+                    final_labels[root] = components.len() as u32;
+                }
+                let label = final_labels[root];
+                labels[x + y * width] = label;
+
+                let component = &mut components[label as usize - 1];
+                component.area += 1;
+                component.min_x = component.min_x.min(x);
+                component.max_x = component.max_x.max(x);
+                component.max_y = y;
+                component.centroid_x += x as f64;
+                component.centroid_y += y as f64;
+                component.sum += self.get_unchecked(x, y).to_f64();
+            }
+        }
+        for component in components.iter_mut() {
+            component.centroid_x /= component.area as f64;
+            component.centroid_y /= component.area as f64;
+        }
+        (Table::new(Array::from_vec(labels), width, height), components)
+    }
+}
@@ -0,0 +1,196 @@
+//! Reading and writing Tables in CSV (comma-separated values) and TSV (tab-separated values)
+//! format, so that they can be used in spreadsheets. Every line contains one row of the table.
+//! Optionally, the first line is a header that contains the column indices 0 to width - 1.
+
+use crate::Array;
+use crate::Table;
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::str::FromStr;
+
+/// Describes the text format of a table: the delimiter between the values and whether the
+/// first line is a header with column indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvFormat {
+
+    pub delimiter: char,
+    pub header: bool
+}
+
+impl CsvFormat {
+
+    /// Comma-separated values without header
+    pub fn csv() -> CsvFormat {
+        CsvFormat { delimiter: ',', header: false }
+    }
+
+    /// Tab-separated values without header
+    pub fn tsv() -> CsvFormat {
+        CsvFormat { delimiter: '\t', header: false }
+    }
+
+    /// Returns a copy of this format with the given header setting
+    pub fn with_header(self, header: bool) -> CsvFormat {
+        CsvFormat { delimiter: self.delimiter, header }
+    }
+}
+
+impl Default for CsvFormat {
+
+    fn default() -> CsvFormat {
+        CsvFormat::csv()
+    }
+}
+
+/// The error type of Table::from_csv. Line and column numbers start at 1, and the column
+/// number is the position of the value within its line.
+#[derive(Debug)]
+pub enum CsvError {
+
+    /// Reading failed
+    Io(io::Error),
+
+    /// The input doesn't contain any rows
+    Empty,
+
+    /// The value at the given line and column can't be parsed
+    InvalidValue { line: usize, column: usize, value: String },
+
+    /// The value in the header at the given column is not the column index
+    InvalidHeader { line: usize, column: usize, value: String },
+
+    /// The line contains another number of values than the first row
+    RaggedRow { line: usize, expected: usize, found: usize }
+}
+
+impl Display for CsvError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(error) => write!(f, "I/O error: {}", error),
+            CsvError::Empty => write!(f, "The input doesn't contain any rows"),
+            CsvError::InvalidValue { line, column, value } => {
+                write!(f, "Line {}, column {}: can't parse {:?}", line, column, value)
+            },
+            CsvError::InvalidHeader { line, column, value } => {
+                write!(f, "Line {}, column {}: expected column index {}, but found {:?}", line, column, column - 1, value)
+            },
+            CsvError::RaggedRow { line, expected, found } => {
+                write!(f, "Line {}: expected {} values, but found {}", line, expected, found)
+            }
+        }
+    }
+}
+
+impl Error for CsvError {
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::Io(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+
+    fn from(error: io::Error) -> CsvError {
+        CsvError::Io(error)
+    }
+}
+
+impl<T: Display> Table<T> {
+
+    /// Writes this table to the given writer in the given format, one row per line.
+    pub fn write_csv<W: Write>(&self, writer: W, format: &CsvFormat) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        if format.header {
+            for x in 0..self.get_width() {
+                if x > 0 {
+                    write!(writer, "{}", format.delimiter)?;
+                }
+                write!(writer, "{}", x)?;
+            }
+            writeln!(writer)?;
+        }
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                if x > 0 {
+                    write!(writer, "{}", format.delimiter)?;
+                }
+                write!(writer, "{}", self.get_unchecked_ref(x, y))?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Converts this table to a String in the given format, one row per line.
+    pub fn to_csv_string(&self, format: &CsvFormat) -> String {
+        let mut bytes = Vec::new();
+        self.write_csv(&mut bytes, format).expect("Writing to a Vec can't fail");
+        String::from_utf8(bytes).expect("The Display implementations should produce valid UTF-8")
+    }
+}
+
+impl<T: FromStr> Table<T> {
+
+    /// Reads a table in the given format from the given reader. The width of the table will be the
+    /// number of values on the first row and the height will be the number of rows. Whitespace around
+    /// values is ignored, and so are empty lines. This will return an error with the line and column
+    /// number of the problem if a value can't be parsed or a row has the wrong number of values.
+    pub fn from_csv<R: Read>(reader: R, format: &CsvFormat) -> Result<Table<T>, CsvError> {
+        let mut elements = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        let mut expect_header = format.header;
+
+        for (line_index, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line_number = line_index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let values = line.split(format.delimiter).map(|value| value.trim());
+            if expect_header {
+                for (column_index, value) in values.enumerate() {
+                    if value.parse::<usize>().ok() != Some(column_index) {
+                        return Err(CsvError::InvalidHeader { line: line_number, column: column_index + 1, value: value.to_string() });
+                    }
+                    width = column_index + 1;
+                }
+                expect_header = false;
+                continue;
+            }
+
+            let old_length = elements.len();
+            for (column_index, value) in values.enumerate() {
+                match value.parse() {
+                    Ok(element) => elements.push(element),
+                    Err(_) => return Err(CsvError::InvalidValue { line: line_number, column: column_index + 1, value: value.to_string() })
+                };
+            }
+            let found = elements.len() - old_length;
+            if width == 0 {
+                width = found;
+            } else if found != width {
+                return Err(CsvError::RaggedRow { line: line_number, expected: width, found });
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            return Err(CsvError::Empty);
+        }
+        let array = Array::from_vec(elements);
+        Ok(Table::new(array, width, height))
+    }
+
+    /// Reads a table in the given format from the given string, just like from_csv.
+    pub fn from_csv_str(text: &str, format: &CsvFormat) -> Result<Table<T>, CsvError> {
+        Table::from_csv(text.as_bytes(), format)
+    }
+}
@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::fmt;
+
+/// The error type that is returned by the fallible (try_) methods of the structures in this crate.
+/// Every method that would panic on bad input has a try_ counterpart that returns one of these
+/// errors instead. The variants carry the indices, sizes and dimensions that caused the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryStructuresError {
+
+    /// An attempt was made to create a structure with a size of 0.
+    ZeroSize,
+
+    /// The index is not smaller than the size of the Array.
+    IndexOutOfBounds { index: usize, size: usize },
+
+    /// The range that starts at start_index and contains amount elements doesn't fit in the Array.
+    RangeOutOfBounds { start_index: usize, amount: usize, size: usize },
+
+    /// The width or height of a Table is 0.
+    ZeroDimension { width: usize, height: usize },
+
+    /// The coordinates (x, y) are not within a Table with the given width and height.
+    CoordinatesOutOfBounds { x: usize, y: usize, width: usize, height: usize },
+
+    /// The width, height or depth of a Volume is 0.
+    ZeroVolumeDimension { width: usize, height: usize, depth: usize },
+
+    /// The coordinates (x, y, z) are not within a Volume with the given width, height and depth.
+    VolumeCoordinatesOutOfBounds { x: usize, y: usize, z: usize, width: usize, height: usize, depth: usize },
+
+    /// The index is not within an NdArray with the given shape.
+    NdIndexOutOfBounds { index: Vec<usize>, shape: Vec<usize> },
+
+    /// The shape can't be broadcast to the target shape.
+    IncompatibleShapes { shape: Vec<usize>, target: Vec<usize> },
+
+    /// The axis permutation doesn't contain every axis exactly once.
+    InvalidAxisPermutation { permutation: Vec<usize> },
+
+    /// The rectangle with its top-left corner at (x, y) and the given width and height doesn't
+    /// fit in a Table with the given table_width and table_height.
+    RegionOutOfBounds { x: usize, y: usize, width: usize, height: usize, table_width: usize, table_height: usize },
+
+    /// The rectangle from (min_x, min_y) to (max_x, max_y) (inclusive) is empty or doesn't fit in
+    /// a Table with the given width and height.
+    InvalidRect { min_x: usize, min_y: usize, max_x: usize, max_y: usize, width: usize, height: usize },
+
+    /// A Table has a different width or height than the structure it is used with.
+    DimensionMismatch { expected_width: usize, expected_height: usize, width: usize, height: usize },
+
+    /// The operation requires 2 structures with the same length, but their lengths are different.
+    LengthMismatch { expected_length: usize, length: usize },
+
+    /// The operation requires a square Table, but the width and height are different.
+    NotSquare { width: usize, height: usize },
+
+    /// The minimum of a value range is not smaller than its maximum, or one of them is not finite.
+    InvalidRange,
+
+    /// The row stride of a Table is smaller than its width, which would cause rows to overlap.
+    InvalidStride { row_stride: usize, width: usize },
+
+    /// The column stride and row stride of a Table would cause different elements to share the same index.
+    OverlappingStrides { column_stride: usize, row_stride: usize, width: usize, height: usize },
+
+    /// The backing Array is too small for the requested structure.
+    ArrayTooSmall { required_size: usize, size: usize },
+
+    /// The alignment is not a power of 2 or is smaller than the alignment required by the element type.
+    InvalidAlignment { alignment: usize, minimum_alignment: usize },
+
+    /// The allocator failed to allocate the given number of bytes with the given alignment.
+    AllocationFailed { size_in_bytes: usize, alignment: usize },
+
+    /// Computing an index or size caused an integer overflow.
+    Overflow
+}
+
+impl fmt::Display for MemoryStructuresError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MemoryStructuresError::ZeroSize => write!(f, "Size must not be 0"),
+            MemoryStructuresError::IndexOutOfBounds { index, size } => {
+                write!(f, "Index is {} and size is {}", index, size)
+            },
+            MemoryStructuresError::RangeOutOfBounds { start_index, amount, size } => {
+                write!(f, "start_index is {} and length is {}, but the size of this array is {}", start_index, amount, size)
+            },
+            MemoryStructuresError::ZeroDimension { width, height } => {
+                write!(f, "The width is {} and the height is {}, but neither can be 0", width, height)
+            },
+            MemoryStructuresError::CoordinatesOutOfBounds { x, y, width, height } => {
+                write!(f, "x is {}, width is {}, y is {} and height is {}", x, width, y, height)
+            },
+            MemoryStructuresError::ZeroVolumeDimension { width, height, depth } => {
+                write!(f, "The width is {}, the height is {} and the depth is {}, but none of them can be 0", width, height, depth)
+            },
+            MemoryStructuresError::VolumeCoordinatesOutOfBounds { x, y, z, width, height, depth } => {
+                write!(f, "x is {}, width is {}, y is {}, height is {}, z is {} and depth is {}", x, width, y, height, z, depth)
+            },
+            MemoryStructuresError::NdIndexOutOfBounds { index, shape } => {
+                write!(f, "The index is {:?}, but the shape is {:?}", index, shape)
+            },
+            MemoryStructuresError::IncompatibleShapes { shape, target } => {
+                write!(f, "The shape {:?} can't be broadcast to the shape {:?}", shape, target)
+            },
+            MemoryStructuresError::InvalidAxisPermutation { permutation } => {
+                write!(f, "The axis permutation {:?} doesn't contain every axis exactly once", permutation)
+            },
+            MemoryStructuresError::RegionOutOfBounds { x, y, width, height, table_width, table_height } => {
+                write!(f, "The region at ({}, {}) with width {} and height {} doesn't fit in a table with width {} and height {}",
+                       x, y, width, height, table_width, table_height)
+            },
+            MemoryStructuresError::InvalidRect { min_x, min_y, max_x, max_y, width, height } => {
+                write!(f, "The rectangle from ({}, {}) to ({}, {}) is empty or doesn't fit in a table with width {} and height {}",
+                       min_x, min_y, max_x, max_y, width, height)
+            },
+            MemoryStructuresError::DimensionMismatch { expected_width, expected_height, width, height } => {
+                write!(f, "The width is {} and the height is {}, but they should be {} and {}", width, height, expected_width, expected_height)
+            },
+            MemoryStructuresError::LengthMismatch { expected_length, length } => {
+                write!(f, "The length is {}, but it should be {}", length, expected_length)
+            },
+            MemoryStructuresError::NotSquare { width, height } => {
+                write!(f, "The width is {} and the height is {}, but they must be equal", width, height)
+            },
+            MemoryStructuresError::InvalidRange => {
+                write!(f, "The minimum of the range must be smaller than the maximum, and both must be finite")
+            },
+            MemoryStructuresError::InvalidStride { row_stride, width } => {
+                write!(f, "The row stride is {}, but it must be at least the width {}", row_stride, width)
+            },
+            MemoryStructuresError::OverlappingStrides { column_stride, row_stride, width, height } => {
+                write!(f, "The column stride {} and row stride {} cause overlapping elements in a table with width {} and height {}",
+                       column_stride, row_stride, width, height)
+            },
+            MemoryStructuresError::ArrayTooSmall { required_size, size } => {
+                write!(f, "The required size is {}, but the size of the array is {}", required_size, size)
+            },
+            MemoryStructuresError::InvalidAlignment { alignment, minimum_alignment } => {
+                write!(f, "The alignment is {}, but it must be a power of 2 that is at least {}", alignment, minimum_alignment)
+            },
+            MemoryStructuresError::AllocationFailed { size_in_bytes, alignment } => {
+                write!(f, "Failed to allocate {} bytes with an alignment of {}", size_in_bytes, alignment)
+            },
+            MemoryStructuresError::Overflow => write!(f, "Integer overflow while computing an index or size")
+        }
+    }
+}
+
+impl Error for MemoryStructuresError {}
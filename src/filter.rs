@@ -0,0 +1,226 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+use crate::utility::FromF64;
+
+use std::ops::{Add, AddAssign, Mul};
+
+/// Determines which value is used for coordinates outside a Table during a convolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeMode {
+
+    /// Use the value of the nearest element in the table.
+    Clamp,
+
+    /// Continue at the other side of the table, as if the table is repeated infinitely.
+    Wrap,
+
+    /// Reflect the table around its edge elements, so the element at -1 is the element at 1.
+    Mirror,
+
+    /// Use 0 for every coordinate outside the table.
+    Zero
+}
+
+impl EdgeMode {
+
+    /// Maps the given coordinate to a coordinate within [0, size), or None if the value 0 should be used
+    fn resolve(&self, coordinate: isize, size: usize) -> Option<usize> {
+        let size = size as isize;
+        if coordinate >= 0 && coordinate < size {
+            return Some(coordinate as usize);
+        }
+        match self {
+            EdgeMode::Clamp => Some(coordinate.clamp(0, size - 1) as usize),
+            EdgeMode::Wrap => Some(coordinate.rem_euclid(size) as usize),
+            EdgeMode::Mirror => {
+                if size == 1 {
+                    return Some(0);
+                }
+                let period = 2 * (size - 1);
+                let position = coordinate.rem_euclid(period);
+                Some(if position < size { position } else { period - position } as usize)
+            },
+            EdgeMode::Zero => None
+        }
+    }
+
+    /// Resolves all source coordinates that are needed when a kernel of size kernel_size is applied along
+    /// an axis of the given size. The element at index i + j corresponds to output coordinate i and
+    /// kernel coordinate kernel_size - 1 - j, since convolutions flip the kernel.
+    fn resolve_all(&self, size: usize, kernel_size: usize) -> Vec<Option<usize>> {
+        let flipped_center = (kernel_size - 1 - kernel_size / 2) as isize;
+        (0..size + kernel_size - 1).map(|index| self.resolve(index as isize - flipped_center, size)).collect()
+    }
+}
+
+/// An element type that can be filtered with convolutions, which is implemented for f32 and f64.
+/// The FromF64 conversion is needed to compute the weights of kernels.
+pub trait FilterElement: FromF64 + Copy + Default + Add<Output = Self> + AddAssign + Mul<Output = Self> {}
+
+impl FilterElement for f32 {}
+
+impl FilterElement for f64 {}
+
+impl<T: FilterElement> Table<T> {
+
+    /// Creates a new table with the same width and height whose elements are 0
+    fn create_zeros(&self) -> Table<T> {
+        Table::new(Array::create_filled(self.get_width() * self.get_height(), T::default()), self.get_width(), self.get_height())
+    }
+
+    fn try_check_destination(&self, destination: &Table<T>) -> Result<(), MemoryStructuresError> {
+        if destination.get_width() != self.get_width() || destination.get_height() != self.get_height() {
+            return Err(MemoryStructuresError::DimensionMismatch {
+                expected_width: self.get_width(), expected_height: self.get_height(),
+                width: destination.get_width(), height: destination.get_height()
+            });
+        }
+        Ok(())
+    }
+
+    /// Convolves this table with the given kernel and returns the result as a new table. The center of
+    /// the kernel is at (kernel_width / 2, kernel_height / 2), and the element at (x, y) of the result is
+    /// the sum of kernel(kx, ky) * source(x - kx + center_x, y - ky + center_y) over the whole kernel.
+    /// Just like in the mathematical definition of convolution, the kernel is flipped, which only matters
+    /// for asymmetric kernels like the Sobel kernels.
+    /// The edge mode determines the values of the coordinates outside this table.
+    pub fn convolve<K: Copy + Into<T>>(&self, kernel: &Table<K>, edge_mode: EdgeMode) -> Table<T> {
+        let result = self.create_zeros();
+        self.convolve_unchecked_into(kernel, edge_mode, &result);
+        result
+    }
+
+    /// Convolves this table with the given kernel, just like convolve, but stores the result in the given
+    /// destination instead of a new table. This avoids an allocation when a table is filtered repeatedly.
+    /// The destination must not share its elements with this table. This will panic if the destination
+    /// doesn't have the same width and height as this table.
+    pub fn convolve_into<K: Copy + Into<T>>(&self, kernel: &Table<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        if let Err(error) = self.try_convolve_into(kernel, edge_mode, destination) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Convolves this table with the given kernel and stores the result in the given destination, just
+    /// like convolve_into. Instead of panicking, this will return an error.
+    pub fn try_convolve_into<K: Copy + Into<T>>(&self, kernel: &Table<K>, edge_mode: EdgeMode, destination: &Table<T>) -> Result<(), MemoryStructuresError> {
+        self.try_check_destination(destination)?;
+        self.convolve_unchecked_into(kernel, edge_mode, destination);
+        Ok(())
+    }
+
+    fn convolve_unchecked_into<K: Copy + Into<T>>(&self, kernel: &Table<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        let (kernel_width, kernel_height) = (kernel.get_width(), kernel.get_height());
+        let source_x = edge_mode.resolve_all(self.get_width(), kernel_width);
+        let source_y = edge_mode.resolve_all(self.get_height(), kernel_height);
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                let mut sum = T::default();
+                for flipped_y in 0..kernel_height {
+                    if let Some(sy) = source_y[y + flipped_y] {
+                        for flipped_x in 0..kernel_width {
+                            if let Some(sx) = source_x[x + flipped_x] {
+                                let weight = kernel.get_unchecked(kernel_width - 1 - flipped_x, kernel_height - 1 - flipped_y);
+                                sum += weight.into() * self.get_unchecked(sx, sy);
+                            }
+                        }
+                    }
+                }
+                destination.set_unchecked(x, y, sum);
+            }
+        }
+    }
+
+    /// Convolves every row of this table with the given 1-dimensional kernel and returns the result as a
+    /// new table. Convolving the rows and then the columns with 1-dimensional kernels is equivalent to
+    /// convolving with their 2-dimensional product, but much faster for large kernels.
+    pub fn convolve_rows<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode) -> Table<T> {
+        let result = self.create_zeros();
+        self.convolve_unchecked_rows_into(kernel, edge_mode, &result);
+        result
+    }
+
+    /// Convolves every row of this table with the given kernel, just like convolve_rows, but stores the
+    /// result in the given destination, which must not share its elements with this table. This will
+    /// panic if the destination doesn't have the same width and height as this table.
+    pub fn convolve_rows_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        if let Err(error) = self.try_convolve_rows_into(kernel, edge_mode, destination) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Convolves every row of this table and stores the result in the given destination, just like
+    /// convolve_rows_into. Instead of panicking, this will return an error.
+    pub fn try_convolve_rows_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>) -> Result<(), MemoryStructuresError> {
+        self.try_check_destination(destination)?;
+        self.convolve_unchecked_rows_into(kernel, edge_mode, destination);
+        Ok(())
+    }
+
+    fn convolve_unchecked_rows_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        let source_x = edge_mode.resolve_all(self.get_width(), kernel.len());
+        let weights: Vec<T> = (0..kernel.len()).rev().map(|index| kernel.get_unchecked(index).into()).collect();
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                let mut sum = T::default();
+                for (weight, sx) in weights.iter().zip(&source_x[x..]) {
+                    if let Some(sx) = sx {
+                        sum += *weight * self.get_unchecked(*sx, y);
+                    }
+                }
+                destination.set_unchecked(x, y, sum);
+            }
+        }
+    }
+
+    /// Convolves every column of this table with the given 1-dimensional kernel and returns the result
+    /// as a new table.
+    pub fn convolve_columns<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode) -> Table<T> {
+        let result = self.create_zeros();
+        self.convolve_unchecked_columns_into(kernel, edge_mode, &result);
+        result
+    }
+
+    /// Convolves every column of this table with the given kernel, just like convolve_columns, but stores
+    /// the result in the given destination, which must not share its elements with this table. This will
+    /// panic if the destination doesn't have the same width and height as this table.
+    pub fn convolve_columns_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        if let Err(error) = self.try_convolve_columns_into(kernel, edge_mode, destination) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Convolves every column of this table and stores the result in the given destination, just like
+    /// convolve_columns_into. Instead of panicking, this will return an error.
+    pub fn try_convolve_columns_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>) -> Result<(), MemoryStructuresError> {
+        self.try_check_destination(destination)?;
+        self.convolve_unchecked_columns_into(kernel, edge_mode, destination);
+        Ok(())
+    }
+
+    fn convolve_unchecked_columns_into<K: Copy + Into<T>>(&self, kernel: &Array<K>, edge_mode: EdgeMode, destination: &Table<T>){
+        let source_y = edge_mode.resolve_all(self.get_height(), kernel.len());
+        let weights: Vec<T> = (0..kernel.len()).rev().map(|index| kernel.get_unchecked(index).into()).collect();
+
+        // Iterating over the rows in the outer loop keeps the memory access pattern row by row
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                destination.set_unchecked(x, y, T::default());
+            }
+            for (weight, sy) in weights.iter().zip(&source_y[y..]) {
+                if let Some(sy) = sy {
+                    for x in 0..self.get_width() {
+                        *destination.get_unchecked_mut_ref(x, y) += *weight * self.get_unchecked(x, *sy);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Blurs this table with a Gaussian kernel with the given standard deviation, by convolving the rows
+    /// and columns with kernels::gaussian_1d. The result is returned as a new table.
+    pub fn gaussian_blur(&self, sigma: f64, edge_mode: EdgeMode) -> Table<T> {
+        let kernel = crate::kernels::gaussian_1d::<T>(sigma);
+        self.convolve_rows(&kernel, edge_mode).convolve_columns(&kernel, edge_mode)
+    }
+}
@@ -60,19 +60,17 @@ pub trait Graphics2D<T: Copy> {
 
             let negative_slope = (x1 == min_x) ^ (y1 == min_y);
 
-            let end_x;
-            if max_x >= width {
-                end_x = width - 1 - min_x;
+            let end_x = if max_x >= width {
+                width - 1 - min_x
             } else {
-                end_x = max_x - min_x;
-            }
+                max_x - min_x
+            };
 
-            let end_y;
-            if max_y >= height {
-                end_y = height - 1 - min_y;
+            let end_y = if max_y >= height {
+                height - 1 - min_y
             } else {
-                end_y = max_y - min_y;
-            }
+                max_y - min_y
+            };
 
             let mut check_overflow = false;
             {
@@ -139,4 +137,130 @@ pub trait Graphics2D<T: Copy> {
     fn get_width(&self) -> usize;
 
     fn get_height(&self) -> usize;
-}
\ No newline at end of file
+}
+
+/// The 3-dimensional counterpart of Graphics2D.
+pub trait Graphics3D<T: Copy> {
+
+    /// Adds the given value to all points on the line from (x1, y1, z1) to (x2, y2, z2), including
+    /// both end points. The points are chosen with the 3-dimensional Bresenham algorithm, so every
+    /// point on the line will be visited exactly once. Points outside the bounds are skipped, and the
+    /// time needed is proportional to the part of the line that is within bounds.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_line(&self, x1: usize, y1: usize, z1: usize, x2: usize, y2: usize, z2: usize, value: T){
+        let bounds = [self.get_width() as i128, self.get_height() as i128, self.get_depth() as i128];
+        let start = [x1 as i128, y1 as i128, z1 as i128];
+        let end = [x2 as i128, y2 as i128, z2 as i128];
+
+        let mut deltas = [0; 3];
+        let mut steps = [0; 3];
+        for axis in 0..3 {
+            deltas[axis] = (end[axis] - start[axis]).abs();
+            steps[axis] = (end[axis] - start[axis]).signum();
+        }
+
+        // The driving axis is the axis along which the line is the longest
+        let driving_axis = if deltas[0] >= deltas[1] && deltas[0] >= deltas[2] {
+            0
+        } else if deltas[1] >= deltas[2] {
+            1
+        } else {
+            2
+        };
+        let other_axis1 = (driving_axis + 1) % 3;
+        let other_axis2 = (driving_axis + 2) % 3;
+        let length = deltas[driving_axis];
+
+        // The coordinate along the given axis after the given number of steps along the driving axis
+        let coordinate = |axis: usize, driving_steps: i128| {
+            start[axis] + steps[axis] * bresenham_state(deltas[axis], length, driving_steps).0
+        };
+        let is_inside = |axis: usize, driving_steps: i128| coordinate(axis, driving_steps) < bounds[axis];
+
+        // Since every coordinate changes monotonically, the points within bounds form a single segment,
+        // which can be found with a binary search along each axis
+        let mut first_step = 0;
+        let mut last_step = length;
+        for (axis, step) in steps.iter().enumerate() {
+            if *step >= 0 {
+                if !is_inside(axis, 0) {
+                    return;
+                }
+                if !is_inside(axis, length) {
+                    let (mut inside, mut outside) = (0, length);
+                    while outside - inside > 1 {
+                        let middle = inside + (outside - inside) / 2;
+                        if is_inside(axis, middle) { inside = middle; } else { outside = middle; }
+                    }
+                    last_step = last_step.min(inside);
+                }
+            } else {
+                if !is_inside(axis, length) {
+                    return;
+                }
+                if !is_inside(axis, 0) {
+                    let (mut outside, mut inside) = (0, length);
+                    while inside - outside > 1 {
+                        let middle = outside + (inside - outside) / 2;
+                        if is_inside(axis, middle) { inside = middle; } else { outside = middle; }
+                    }
+                    first_step = first_step.max(inside);
+                }
+            }
+        }
+        if first_step > last_step {
+            return;
+        }
+
+        let mut point = [0; 3];
+        for (axis, coordinate_value) in point.iter_mut().enumerate() {
+            *coordinate_value = coordinate(axis, first_step);
+        }
+        let mut error1 = bresenham_state(deltas[other_axis1], length, first_step).1;
+        let mut error2 = bresenham_state(deltas[other_axis2], length, first_step).1;
+        for _ in first_step..=last_step {
+            self.add_unchecked(point[0] as usize, point[1] as usize, point[2] as usize, value);
+
+            point[driving_axis] += steps[driving_axis];
+            if error1 >= 0 {
+                point[other_axis1] += steps[other_axis1];
+                error1 -= 2 * length;
+            }
+            if error2 >= 0 {
+                point[other_axis2] += steps[other_axis2];
+                error2 -= 2 * length;
+            }
+            error1 += 2 * deltas[other_axis1];
+            error2 += 2 * deltas[other_axis2];
+        }
+    }
+
+    fn add_unchecked(&self, x: usize, y: usize, z: usize, amount: T);
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_unchecked_box(&self, min_x: usize, min_y: usize, min_z: usize, max_x: usize, max_y: usize, max_z: usize, amount: T);
+
+    fn get_width(&self) -> usize;
+
+    fn get_height(&self) -> usize;
+
+    fn get_depth(&self) -> usize;
+}
+
+/// Computes the state of the Bresenham algorithm for an axis with the given delta after the given number of
+/// steps along the driving axis, whose delta is length. The first value is the number of steps that were
+/// taken along the axis, which is delta * driving_steps / length rounded to the nearest integer (halves are
+/// rounded up). The second value is the error term of the axis at that moment.
+fn bresenham_state(delta: i128, length: i128, driving_steps: i128) -> (i128, i128) {
+    if length == 0 {
+        return (0, 0);
+    }
+
+    // The product can be almost 2^128, so it needs an unsigned type
+    let product = delta as u128 * driving_steps as u128;
+    let (quotient, remainder) = ((product / length as u128) as i128, (product % length as u128) as i128);
+    let round_up = 2 * remainder >= length;
+    let steps = quotient + round_up as i128;
+    let error = 2 * delta - length + 2 * (remainder - if round_up { length } else { 0 });
+    (steps, error)
+}
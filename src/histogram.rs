@@ -0,0 +1,283 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+use crate::utility::{FromF64, ToF64};
+
+/// Counts how many values fall in each of a number of equally wide bins that together cover the range
+/// from min to max. Every bin contains its lower bound, and the last bin also contains max. Values
+/// outside the range are not put in any bin, but they are counted separately.
+pub struct Histogram {
+
+    counts: Array<u64>,
+    min: f64,
+    max: f64,
+    outside_count: u64
+}
+
+impl Histogram {
+
+    /// Creates an empty histogram with the given number of bins over the range (min, max). This will
+    /// panic if there are 0 bins or if the range is invalid.
+    pub fn new(bins: usize, range: (f64, f64)) -> Histogram {
+        match Histogram::try_new(bins, range) {
+            Ok(histogram) => histogram,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates an empty histogram, just like new. Instead of panicking, this will return an error.
+    pub fn try_new(bins: usize, range: (f64, f64)) -> Result<Histogram, MemoryStructuresError> {
+        let (min, max) = range;
+        if !min.is_finite() || !max.is_finite() || min >= max {
+            return Err(MemoryStructuresError::InvalidRange);
+        }
+        Ok(Histogram { counts: Array::try_create_filled(bins, 0)?, min, max, outside_count: 0 })
+    }
+
+    pub fn get_bin_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn get_range(&self) -> (f64, f64) {
+        (self.min, self.max)
+    }
+
+    /// The number of values in each bin
+    pub fn get_counts(&self) -> &Array<u64> {
+        &self.counts
+    }
+
+    /// The number of values in the given bin. This will panic if the bin is not smaller than get_bin_count().
+    pub fn get_count(&self, bin: usize) -> u64 {
+        self.counts.get(bin)
+    }
+
+    /// The number of values that were outside the range (or NaN)
+    pub fn get_outside_count(&self) -> u64 {
+        self.outside_count
+    }
+
+    /// The number of values in all bins together
+    pub fn get_total(&self) -> u64 {
+        self.counts.sum()
+    }
+
+    /// The lower and upper bound of the given bin
+    pub fn get_bin_range(&self, bin: usize) -> (f64, f64) {
+        let width = self.get_bin_width();
+        (self.min + bin as f64 * width, self.min + (bin + 1) as f64 * width)
+    }
+
+    fn get_bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    /// Gets the bin that contains the given value, or None if the value is outside the range
+    pub fn bin_for(&self, value: f64) -> Option<usize> {
+        if !(value >= self.min && value <= self.max) {
+            return None;
+        }
+        let bin = ((value - self.min) / self.get_bin_width()) as usize;
+        Some(bin.min(self.counts.len() - 1))
+    }
+
+    /// Counts the given value in the bin that contains it
+    pub fn add_value(&mut self, value: f64){
+        match self.bin_for(value) {
+            Some(bin) => self.counts.add(bin, 1),
+            None => self.outside_count += 1
+        }
+    }
+
+    /// Creates the cumulative histogram: the element at index i is the number of values in bins 0 to i.
+    pub fn cumulative(&self) -> Array<u64> {
+        self.counts.inclusive_scan()
+    }
+
+    /// Estimates the value below which the given percentage of the values in the bins lies, assuming
+    /// that the values in each bin are spread evenly over the bin. This returns None if the percentage
+    /// is not between 0 and 100 or if all bins are empty.
+    pub fn percentile(&self, percentage: f64) -> Option<f64> {
+        let total = self.get_total();
+        if !(0.0..=100.0).contains(&percentage) || total == 0 {
+            return None;
+        }
+        let target = percentage / 100.0 * total as f64;
+        let mut counted = 0;
+        for bin in 0..self.counts.len() {
+            let count = self.counts.get_unchecked(bin);
+            if count > 0 && (counted + count) as f64 >= target {
+                let (lower, upper) = self.get_bin_range(bin);
+                let fraction = ((target - counted as f64) / count as f64).max(0.0);
+                return Some(lower + fraction * (upper - lower));
+            }
+            counted += count;
+        }
+        Some(self.max)
+    }
+}
+
+/// An element type that can be counted in a Histogram.
+pub trait HistogramElement: ToF64 + Copy {
+
+    /// Counts all given values in the histogram. The default implementation finds the bin of each value
+    /// separately, but small integer types can override this.
+    fn count_values<I: Iterator<Item = Self>>(values: I, histogram: &mut Histogram){
+        for value in values {
+            histogram.add_value(value.to_f64());
+        }
+    }
+}
+
+/// Counts the values with a lookup table that contains the bin of every possible value, which avoids
+/// floating point computations for each value.
+fn count_with_lookup_table<I: Iterator<Item = usize>>(values: I, possible_values: usize, histogram: &mut Histogram){
+    let bins: Vec<Option<usize>> = (0..possible_values).map(|value| histogram.bin_for(value as f64)).collect();
+    let mut counts = vec![0u64; histogram.get_bin_count()];
+    for value in values {
+        match bins[value] {
+            Some(bin) => counts[bin] += 1,
+            None => histogram.outside_count += 1
+        }
+    }
+    for (bin, count) in counts.into_iter().enumerate() {
+        histogram.counts.add_unchecked(bin, count);
+    }
+}
+
+impl HistogramElement for u8 {
+
+    fn count_values<I: Iterator<Item = Self>>(values: I, histogram: &mut Histogram){
+        count_with_lookup_table(values.map(|value| value as usize), 1 << 8, histogram);
+    }
+}
+
+impl HistogramElement for u16 {
+
+    fn count_values<I: Iterator<Item = Self>>(values: I, histogram: &mut Histogram){
+        count_with_lookup_table(values.map(|value| value as usize), 1 << 16, histogram);
+    }
+}
+
+impl HistogramElement for usize {}
+impl HistogramElement for isize {}
+impl HistogramElement for u32 {}
+impl HistogramElement for u64 {}
+impl HistogramElement for u128 {}
+impl HistogramElement for i8 {}
+impl HistogramElement for i16 {}
+impl HistogramElement for i32 {}
+impl HistogramElement for i64 {}
+impl HistogramElement for i128 {}
+impl HistogramElement for f32 {}
+impl HistogramElement for f64 {}
+
+/// Computes the value that equalization assigns to the values in each bin: the lower bound of the range
+/// plus the fraction of the values in lower bins (ignoring the lowest non-empty bin) times the range width.
+fn equalization_mapping(histogram: &Histogram) -> Vec<f64> {
+    let cumulative = histogram.cumulative();
+    let total = histogram.get_total();
+    let lowest = (0..cumulative.len()).map(|bin| cumulative.get_unchecked(bin)).find(|count| *count > 0).unwrap_or(0);
+    let (min, max) = histogram.get_range();
+    (0..cumulative.len()).map(|bin| {
+        if total == lowest {
+            return min;
+        }
+        let fraction = cumulative.get_unchecked(bin).saturating_sub(lowest) as f64 / (total - lowest) as f64;
+        min + fraction * (max - min)
+    }).collect()
+}
+
+impl<T: HistogramElement> Array<T> {
+
+    /// Creates a histogram of the elements of this Array with the given number of bins over the given range.
+    /// This will panic if there are 0 bins or if the range is invalid.
+    pub fn histogram(&self, bins: usize, range: (f64, f64)) -> Histogram {
+        match self.try_histogram(bins, range) {
+            Ok(histogram) => histogram,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a histogram of the elements of this Array, just like histogram.
+    /// Instead of panicking, this will return an error.
+    pub fn try_histogram(&self, bins: usize, range: (f64, f64)) -> Result<Histogram, MemoryStructuresError> {
+        let mut histogram = Histogram::try_new(bins, range)?;
+        T::count_values(self.as_slice().iter().copied(), &mut histogram);
+        Ok(histogram)
+    }
+}
+
+impl<T: HistogramElement + FromF64> Array<T> {
+
+    /// Performs histogram equalization on the elements of this Array: the elements inside the range are
+    /// replaced such that their histogram with the given number of bins becomes approximately flat over
+    /// the range. The order of the elements is preserved, and elements outside the range are not changed.
+    /// For u8 elements, using 256 bins and the range (0.0, 255.0) gives the classical equalization.
+    /// This will panic if there are 0 bins or if the range is invalid.
+    pub fn equalize(&self, bins: usize, range: (f64, f64)){
+        if let Err(error) = self.try_equalize(bins, range) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs histogram equalization on the elements of this Array, just like equalize.
+    /// Instead of panicking, this will return an error.
+    pub fn try_equalize(&self, bins: usize, range: (f64, f64)) -> Result<(), MemoryStructuresError> {
+        let histogram = self.try_histogram(bins, range)?;
+        let mapping = equalization_mapping(&histogram);
+        for element in self.as_mut_slice().iter_mut() {
+            if let Some(bin) = histogram.bin_for(element.to_f64()) {
+                *element = T::from_f64(mapping[bin]);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: HistogramElement> Table<T> {
+
+    /// Creates a histogram of the elements of this table with the given number of bins over the given range.
+    /// This will panic if there are 0 bins or if the range is invalid.
+    pub fn histogram(&self, bins: usize, range: (f64, f64)) -> Histogram {
+        match self.try_histogram(bins, range) {
+            Ok(histogram) => histogram,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a histogram of the elements of this table, just like histogram.
+    /// Instead of panicking, this will return an error.
+    pub fn try_histogram(&self, bins: usize, range: (f64, f64)) -> Result<Histogram, MemoryStructuresError> {
+        let mut histogram = Histogram::try_new(bins, range)?;
+        T::count_values((0..self.get_height()).flat_map(|y| self.row_iter(y).copied()), &mut histogram);
+        Ok(histogram)
+    }
+}
+
+impl<T: HistogramElement + FromF64> Table<T> {
+
+    /// Performs histogram equalization on the elements of this table, just like Array::equalize.
+    /// This will panic if there are 0 bins or if the range is invalid.
+    pub fn equalize(&self, bins: usize, range: (f64, f64)){
+        if let Err(error) = self.try_equalize(bins, range) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs histogram equalization on the elements of this table, just like equalize.
+    /// Instead of panicking, this will return an error.
+    pub fn try_equalize(&self, bins: usize, range: (f64, f64)) -> Result<(), MemoryStructuresError> {
+        let histogram = self.try_histogram(bins, range)?;
+        let mapping = equalization_mapping(&histogram);
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                let element = self.get_unchecked_mut_ref(x, y);
+                if let Some(bin) = histogram.bin_for(element.to_f64()) {
+                    *element = T::from_f64(mapping[bin]);
+                }
+            }
+        }
+        Ok(())
+    }
+}
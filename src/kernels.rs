@@ -0,0 +1,115 @@
+//! Commonly used kernels for Table::convolve, Table::convolve_rows and Table::convolve_columns, and
+//! structuring elements for the morphological operations like Table::erode and Table::dilate.
+//! The center of every kernel and structuring element is at (width / 2, height / 2).
+
+use crate::Array;
+use crate::FilterElement;
+use crate::Table;
+
+/// Creates a kernel with the given width and height whose elements are all 1 / (width * height),
+/// which computes the average of the neighborhood of each element. This will panic if the width
+/// or height is 0.
+pub fn box_filter<T: FilterElement>(width: usize, height: usize) -> Table<T> {
+    let size = width * height;
+    Table::new(Array::create_filled(size, T::from_f64(1.0 / size as f64)), width, height)
+}
+
+/// Creates a 1-dimensional kernel with the given size whose elements are all 1 / size. Convolving the
+/// rows and columns with this kernel is equivalent to convolving with box_filter(size, size).
+/// This will panic if the size is 0.
+pub fn box_filter_1d<T: FilterElement>(size: usize) -> Array<T> {
+    Array::create_filled(size, T::from_f64(1.0 / size as f64))
+}
+
+/// Computes the weights of a normalized 1-dimensional Gaussian kernel with a radius of 3 sigma
+fn gaussian_weights(sigma: f64) -> Vec<f64> {
+    if sigma.is_nan() || sigma <= 0.0 {
+        panic!("sigma is {}, but it must be positive", sigma);
+    }
+    let radius = (3.0 * sigma).ceil() as isize;
+    let weights: Vec<f64> = (-radius..=radius).map(|offset| (-((offset * offset) as f64) / (2.0 * sigma * sigma)).exp()).collect();
+    let total: f64 = weights.iter().sum();
+    weights.into_iter().map(|weight| weight / total).collect()
+}
+
+/// Creates a 1-dimensional Gaussian kernel with the given standard deviation. The kernel has a size of
+/// 2 * ceil(3 * sigma) + 1 and its elements sum to 1. This will panic if sigma is not positive.
+pub fn gaussian_1d<T: FilterElement>(sigma: f64) -> Array<T> {
+    Array::from_vec(gaussian_weights(sigma).into_iter().map(T::from_f64).collect())
+}
+
+/// Creates a square 2-dimensional Gaussian kernel with the given standard deviation, which is the
+/// product of gaussian_1d with itself. Using convolve_rows and convolve_columns with gaussian_1d
+/// gives the same result and is faster. This will panic if sigma is not positive.
+pub fn gaussian<T: FilterElement>(sigma: f64) -> Table<T> {
+    let weights = gaussian_weights(sigma);
+    let mut elements = Vec::with_capacity(weights.len() * weights.len());
+    for weight_y in weights.iter() {
+        for weight_x in weights.iter() {
+            elements.push(T::from_f64(weight_x * weight_y));
+        }
+    }
+    Table::new(Array::from_vec(elements), weights.len(), weights.len())
+}
+
+fn create_3x3<T: FilterElement>(weights: [f64; 9]) -> Table<T> {
+    Table::new(Array::from_vec(weights.iter().map(|weight| T::from_f64(*weight)).collect()), 3, 3)
+}
+
+/// Creates the 3x3 Sobel kernel that responds to changes in the x-direction. The result of a convolution
+/// with this kernel is positive when the values increase with x.
+pub fn sobel_x<T: FilterElement>() -> Table<T> {
+    create_3x3([
+        1.0, 0.0, -1.0,
+        2.0, 0.0, -2.0,
+        1.0, 0.0, -1.0
+    ])
+}
+
+/// Creates the 3x3 Sobel kernel that responds to changes in the y-direction. The result of a convolution
+/// with this kernel is positive when the values increase with y.
+pub fn sobel_y<T: FilterElement>() -> Table<T> {
+    create_3x3([
+        1.0, 2.0, 1.0,
+        0.0, 0.0, 0.0,
+        -1.0, -2.0, -1.0
+    ])
+}
+
+/// Creates the 3x3 Laplacian kernel (using the 4 direct neighbors), which computes the sum of the
+/// neighbors minus 4 times the element itself.
+pub fn laplacian<T: FilterElement>() -> Table<T> {
+    create_3x3([
+        0.0, 1.0, 0.0,
+        1.0, -4.0, 1.0,
+        0.0, 1.0, 0.0
+    ])
+}
+
+/// Creates a square structuring element of size by size true elements. This will panic if the size is 0.
+pub fn square_element(size: usize) -> Table<bool> {
+    Table::new(Array::create_filled(size * size, true), size, size)
+}
+
+/// Creates a 3x3 structuring element that contains the center and its 4 direct neighbors.
+pub fn cross_element() -> Table<bool> {
+    Table::new(Array::from_vec(vec![
+        false, true, false,
+        true, true, true,
+        false, true, false
+    ]), 3, 3)
+}
+
+/// Creates a (2 * radius + 1) by (2 * radius + 1) structuring element that contains all positions whose
+/// Euclidean distance to the center is at most the radius.
+pub fn disk_element(radius: usize) -> Table<bool> {
+    let size = 2 * radius + 1;
+    let element = Table::new(Array::create_filled(size * size, false), size, size);
+    for y in 0..size {
+        for x in 0..size {
+            let (dx, dy) = (x.abs_diff(radius), y.abs_diff(radius));
+            element.set_unchecked(x, y, dx * dx + dy * dy <= radius * radius);
+        }
+    }
+    element
+}
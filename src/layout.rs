@@ -0,0 +1,154 @@
+use crate::MemoryStructuresError;
+
+use std::convert::TryFrom;
+
+/// The order in which the elements of a Table are stored in its backing Array. The layout only
+/// affects performance: all Table methods give the same results for every layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableLayout {
+
+    /// The element at (x, y) is stored at index x + y * width. Operations on rows are the fastest.
+    RowMajor,
+
+    /// The element at (x, y) is stored at index y + x * height. Operations on columns are the fastest.
+    ColumnMajor,
+
+    /// The table is divided into tiles of tile_width by tile_height elements. The tiles are stored
+    /// row by row and the elements within each tile are also stored row by row. If the width or height
+    /// is not a multiple of the tile size, the tiles in the last column or row are padded, so the
+    /// backing Array needs some extra elements. This is cache-friendly for neighborhood operations.
+    Tiled { tile_width: usize, tile_height: usize },
+
+    /// The element at (x, y) is stored at the index whose bits alternate between the bits of x and
+    /// the bits of y (the Z-order curve). This is cache-friendly for neighborhood operations, but needs
+    /// extra elements in the backing Array unless the width and height are the same power of 2.
+    Morton
+}
+
+impl TableLayout {
+
+    /// Computes the minimum number of elements that the backing Array of a table with this layout and
+    /// the given width and height must have. This will panic if the width or height is 0, if a tile
+    /// dimension is 0 or if the size would overflow.
+    pub fn required_size(&self, width: usize, height: usize) -> usize {
+        match self.try_required_size(width, height) {
+            Ok(size) => size,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the minimum size of the backing Array, just like required_size.
+    /// Instead of panicking, this will return an error.
+    pub fn try_required_size(&self, width: usize, height: usize) -> Result<usize, MemoryStructuresError> {
+        if width == 0 || height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
+        }
+        match *self {
+            TableLayout::RowMajor | TableLayout::ColumnMajor => width.checked_mul(height).ok_or(MemoryStructuresError::Overflow),
+            TableLayout::Tiled { tile_width, tile_height } => {
+                if tile_width == 0 || tile_height == 0 {
+                    return Err(MemoryStructuresError::ZeroDimension { width: tile_width, height: tile_height });
+                }
+                let padded_width = width.div_ceil(tile_width).checked_mul(tile_width);
+                let padded_height = height.div_ceil(tile_height).checked_mul(tile_height);
+                padded_width.zip(padded_height).and_then(|(padded_width, padded_height)| padded_width.checked_mul(padded_height))
+                    .ok_or(MemoryStructuresError::Overflow)
+            },
+            TableLayout::Morton => {
+                // The Morton index increases with both x and y, so the last element has the largest index
+                if width - 1 > u32::MAX as usize || height - 1 > u32::MAX as usize {
+                    return Err(MemoryStructuresError::Overflow);
+                }
+                let last_index = interleave_bits(width as u64 - 1, height as u64 - 1);
+                last_index.checked_add(1).and_then(|size| usize::try_from(size).ok()).ok_or(MemoryStructuresError::Overflow)
+            }
+        }
+    }
+
+    /// Creates the indexing for a table with this layout and the given width and height, which must be
+    /// valid for the layout.
+    pub(crate) fn indexing(&self, width: usize, height: usize) -> TableIndexing {
+        match *self {
+            TableLayout::RowMajor => TableIndexing::Strided { column_stride: 1, row_stride: width },
+            TableLayout::ColumnMajor => TableIndexing::Strided { column_stride: height, row_stride: 1 },
+            TableLayout::Tiled { tile_width, tile_height } => TableIndexing::Tiled {
+                origin_x: 0, origin_y: 0, tile_width, tile_height, tiles_per_row: width.div_ceil(tile_width)
+            },
+            TableLayout::Morton => TableIndexing::Morton { origin_x: 0, origin_y: 0 }
+        }
+    }
+}
+
+/// Describes how the coordinates of a Table are mapped to indices in its backing Array, relative to
+/// the offset of the table (see Table::get_indexing). Sub tables of tiled and Morton tables remember
+/// the position of their (0, 0) in the original table, since their indices can't be expressed with strides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableIndexing {
+
+    /// The element at (x, y) is stored at index x * column_stride + y * row_stride. This is used by
+    /// tables with the row-major or column-major layout, their sub tables, and slices of a Volume.
+    Strided { column_stride: usize, row_stride: usize },
+
+    /// The element at (x, y) is stored at the position of (origin_x + x, origin_y + y) in a table with
+    /// the tiled layout that has tiles_per_row tiles in each row of tiles.
+    Tiled { origin_x: usize, origin_y: usize, tile_width: usize, tile_height: usize, tiles_per_row: usize },
+
+    /// The element at (x, y) is stored at the position of (origin_x + x, origin_y + y) in a table with
+    /// the Morton layout.
+    Morton { origin_x: usize, origin_y: usize }
+}
+
+impl TableIndexing {
+
+    pub(crate) fn index_for(&self, x: usize, y: usize) -> usize {
+        match *self {
+            TableIndexing::Strided { column_stride, row_stride } => x * column_stride + y * row_stride,
+            TableIndexing::Tiled { origin_x, origin_y, tile_width, tile_height, tiles_per_row } => {
+                let (x, y) = (origin_x + x, origin_y + y);
+                let tile_index = (y / tile_height) * tiles_per_row + x / tile_width;
+                (tile_index * tile_height + y % tile_height) * tile_width + x % tile_width
+            },
+            TableIndexing::Morton { origin_x, origin_y } => {
+                interleave_bits((origin_x + x) as u64, (origin_y + y) as u64) as usize
+            }
+        }
+    }
+
+    /// Creates the indexing of the sub table whose (0, 0) is at (x, y) in the table with this indexing.
+    /// The first value is the amount that must be added to the offset of the table.
+    pub(crate) fn shifted(&self, x: usize, y: usize) -> (usize, TableIndexing) {
+        match *self {
+            TableIndexing::Strided { .. } => (self.index_for(x, y), *self),
+            TableIndexing::Tiled { origin_x, origin_y, tile_width, tile_height, tiles_per_row } => (0, TableIndexing::Tiled {
+                origin_x: origin_x + x, origin_y: origin_y + y, tile_width, tile_height, tiles_per_row
+            }),
+            TableIndexing::Morton { origin_x, origin_y } => (0, TableIndexing::Morton { origin_x: origin_x + x, origin_y: origin_y + y })
+        }
+    }
+
+    /// The number of elements of a row, starting at x, that are stored directly after each other
+    pub(crate) fn row_run_length(&self, x: usize) -> usize {
+        match *self {
+            TableIndexing::Strided { column_stride: 1, .. } => usize::MAX,
+            TableIndexing::Strided { .. } => 1,
+            TableIndexing::Tiled { origin_x, tile_width, .. } => tile_width - (origin_x + x) % tile_width,
+            TableIndexing::Morton { origin_x, .. } => 2 - (origin_x + x) % 2
+        }
+    }
+}
+
+/// Interleaves the bits of x and y, which must be smaller than 2^32. The bits of x end up at the
+/// even positions and the bits of y at the odd positions.
+fn interleave_bits(x: u64, y: u64) -> u64 {
+    spread_bits(x) | (spread_bits(y) << 1)
+}
+
+/// Moves bit i of the value to position 2i
+fn spread_bits(value: u64) -> u64 {
+    let mut value = value & 0xFFFF_FFFF;
+    value = (value | (value << 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value | (value << 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value << 2)) & 0x3333_3333_3333_3333;
+    (value | (value << 1)) & 0x5555_5555_5555_5555
+}
@@ -1,11 +1,55 @@
 mod array;
 mod table;
+mod layout;
+mod summed_area;
+mod filter;
+mod transform;
+mod resample;
+mod morphology;
+mod components;
+mod bits;
+mod sparse;
+pub mod kernels;
+mod volume;
+mod ndarray;
 mod graphics;
+mod error;
+mod view;
+mod allocation;
+mod simd;
+mod reduction;
+mod histogram;
+pub mod snapshot;
+mod csv;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(unix)]
+mod mapping;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod utility;
 
 pub use array::Array;
 pub use table::Table;
-pub use graphics::Graphics2D;
+pub use layout::{TableIndexing, TableLayout};
+pub use summed_area::{FenwickTable, SummedAreaTable};
+pub use filter::{EdgeMode, FilterElement};
+pub use resample::ResizeFilter;
+pub use components::{ComponentStats, Connectivity};
+pub use histogram::{Histogram, HistogramElement};
+pub use bits::{BitArray, BitTable};
+pub use sparse::SparseTable;
+pub use volume::{Axis, Volume};
+pub use ndarray::{NdArray, NdIndices};
+pub use graphics::{Graphics2D, Graphics3D};
+pub use error::MemoryStructuresError;
+pub use view::TableView;
+pub use snapshot::{SnapshotElement, SnapshotError};
+pub use csv::{CsvError, CsvFormat};
+#[cfg(feature = "rayon")]
+pub use parallel::TableRowMut;
+#[cfg(unix)]
+pub use mapping::MapMode;
 
 #[cfg(test)]
 mod tests {
@@ -13,6 +57,7 @@ mod tests {
     use crate::Array;
     use crate::Table;
     use crate::Graphics2D;
+    use crate::MemoryStructuresError;
 
     use std::panic::catch_unwind;
 
@@ -34,6 +79,10 @@ mod tests {
 
         array.set_some(0, 0, 13);
         assert_eq!(array.get(0), 74);
+
+        assert_eq!(array.try_create_vector(98, 2), Ok(vec![74, 45]));
+        assert_eq!(array.try_create_vector(200, 0), Ok(vec![]));
+        assert!(array.try_create_vector(99, 2).is_err());
         assert_eq!(array.get(1), 74);
         
         catch_unwind(|| {
@@ -154,6 +203,1469 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_table_rows_and_columns(){
+
+        // Use a rectangular table with an array that is bigger than needed to detect stray writes
+        let array = Array::create_filled(16, 0);
+        let table = unsafe { Table::new(array.sharing_copy(), 5, 3) };
+        for y in 0..3 {
+            for x in 0..5 {
+                table.set(x, y, x + 10 * y);
+            }
+        }
+
+        table.set_column(4, 99);
+        assert_eq!(table.get_column(4), vec![99, 99, 99]);
+        assert_eq!(table.get_column(3), vec![3, 13, 23]);
+        assert_eq!(array.get(15), 0);
+
+        table.add_column(0, 100);
+        assert_eq!(table.get_column(0), vec![100, 110, 120]);
+        table.add_row(1, 1000);
+        assert_eq!(table.get_row(1), vec![1110, 1011, 1012, 1013, 1099]);
+        assert_eq!(table.get_row(2), vec![120, 21, 22, 23, 99]);
+
+        table.copy_column(1, 3);
+        assert_eq!(table.get_column(3), vec![1, 1011, 21]);
+        table.copy_row(0, 2);
+        assert_eq!(table.get_row(2), vec![100, 1, 2, 1, 99]);
+
+        table.swap_columns(0, 4);
+        assert_eq!(table.get_row(0), vec![99, 1, 2, 1, 100]);
+        assert_eq!(table.get_row(1), vec![1099, 1011, 1012, 1011, 1110]);
+        table.swap_rows(0, 1);
+        assert_eq!(table.get_row(0), vec![1099, 1011, 1012, 1011, 1110]);
+        assert_eq!(table.get_row(1), vec![99, 1, 2, 1, 100]);
+        assert_eq!(array.get(15), 0);
+
+        catch_unwind(|| {
+            table.get_column(5);
+        }).unwrap_err();
+        catch_unwind(|| {
+            table.swap_rows(0, 3);
+        }).unwrap_err();
+    }
+
+    #[test]
+    fn test_sub_tables(){
+        let table = Table::new(Array::create_filled(8 * 6, 0), 8, 6);
+        let sub_table = unsafe { table.sub_table(2, 1, 4, 3) };
+        assert_eq!(sub_table.get_width(), 4);
+        assert_eq!(sub_table.get_height(), 3);
+        assert_eq!(sub_table.get_offset(), 10);
+        assert_eq!(sub_table.get_row_stride(), 8);
+        assert!(!sub_table.is_contiguous());
+
+        sub_table.set_all(1);
+        sub_table.set(0, 0, 5);
+        assert_eq!(table.get(2, 1), 5);
+        assert_eq!(table.get(1, 1), 0);
+        assert_eq!(table.get(5, 3), 1);
+        assert_eq!(table.get(6, 3), 0);
+        assert_eq!(table.get(2, 4), 0);
+
+        sub_table.set_column(3, 7);
+        sub_table.add_row(2, 10);
+        assert_eq!(table.get_column(5), vec![0, 7, 7, 17, 0, 0]);
+        assert_eq!(table.get_row(3), vec![0, 0, 11, 11, 11, 17, 0, 0]);
+
+        // Drawing on the view must stay within the view
+        sub_table.set_all(0);
+        sub_table.draw_line(1, 0, 1, 100, 1);
+        sub_table.draw_line(0, 2, 100, 2, 1);
+        assert_eq!(table.get_column(3), vec![0, 1, 1, 2, 0, 0]);
+        assert_eq!(table.get_row(3), vec![0, 0, 1, 2, 1, 1, 0, 0]);
+        assert_eq!(table.get_row(0), vec![0; 8]);
+        assert_eq!(table.get_row(4), vec![0; 8]);
+        assert_eq!(table.get_column(6), vec![0; 6]);
+
+        // A sub table of a sub table
+        let nested = unsafe { sub_table.sub_table(1, 1, 2, 2) };
+        assert_eq!(nested.get_offset(), 19);
+        nested.set_all(9);
+        assert_eq!(table.get_row(2), vec![0, 0, 0, 9, 9, 0, 0, 0]);
+        assert_eq!(table.get_row(3), vec![0, 0, 1, 9, 9, 1, 0, 0]);
+        assert_eq!(table.get(3, 1), 1);
+
+        unsafe {
+            assert!(table.try_sub_table(5, 0, 4, 1).is_err());
+            assert!(table.try_sub_table(0, 0, 0, 1).is_err());
+            catch_unwind(|| {
+                sub_table.sub_table(0, 1, 1, 3);
+            }).unwrap_err();
+        }
+
+        assert!(Table::try_with_stride(Array::create_filled(10, 0), 2, 3, 2, 5).is_ok());
+        assert!(Table::try_with_stride(Array::create_filled(10, 0), 3, 3, 2, 5).is_err());
+        assert!(Table::try_with_stride(Array::create_filled(10, 0), 0, 3, 2, 2).is_err());
+    }
+
+    #[test]
+    fn test_split_tables(){
+        let mut table = Table::new(Array::create_filled(7 * 5, 0u32), 7, 5);
+
+        {
+            let bands = table.split_rows_mut(2);
+            assert_eq!(bands.len(), 3);
+            assert_eq!(bands[2].get_y(), 4);
+            assert_eq!(bands[2].get_height(), 1);
+            std::thread::scope(|scope| {
+                for band in bands {
+                    scope.spawn(move || {
+                        for y in 0..band.get_height() {
+                            band.set_row(y, (band.get_y() + y) as u32);
+                        }
+                    });
+                }
+            });
+        }
+        for y in 0..5 {
+            assert_eq!(table.get_row(y), vec![y as u32; 7]);
+        }
+
+        {
+            let tiles = table.split_tiles_mut(3, 2);
+            assert_eq!(tiles.len(), 9);
+            assert_eq!((tiles[5].get_x(), tiles[5].get_y()), (6, 2));
+            assert_eq!((tiles[5].get_width(), tiles[5].get_height()), (1, 2));
+            std::thread::scope(|scope| {
+                for (tile_index, tile) in tiles.into_iter().enumerate() {
+                    scope.spawn(move || {
+                        tile.set_all(tile_index as u32);
+                        tile.add_unchecked_rect(0, 0, tile.get_width() - 1, tile.get_height() - 1, 100);
+                    });
+                }
+            });
+        }
+        assert_eq!(table.get_row(0), vec![100, 100, 100, 101, 101, 101, 102]);
+        assert_eq!(table.get_row(3), vec![103, 103, 103, 104, 104, 104, 105]);
+        assert_eq!(table.get_row(4), vec![106, 106, 106, 107, 107, 107, 108]);
+
+        // Views can't write into their neighbors, not even with the unchecked Graphics2D methods
+        {
+            let mut tiles = table.split_tiles_mut(3, 2);
+            assert_eq!(Err(MemoryStructuresError::CoordinatesOutOfBounds { x: 3, y: 0, width: 3, height: 2 }), tiles[0].try_set(3, 0, 5));
+            *tiles[0].get_mut_ref(2, 1) = 5;
+            assert_eq!(tiles[0].get(2, 1), 5);
+            let tile = &tiles[0];
+            catch_unwind(|| tile.add_unchecked(3, 0, 1)).unwrap_err();
+            catch_unwind(|| tile.add_unchecked_rect(0, 0, 2, 2, 1)).unwrap_err();
+            tile.draw_line(0, 0, 10, 0, 1);
+        }
+        assert_eq!(table.get_row(0), vec![101, 101, 101, 101, 101, 101, 102]);
+        assert_eq!(table.get(2, 1), 5);
+
+        catch_unwind(move || {
+            table.split_tiles_mut(0, 1);
+        }).unwrap_err();
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel(){
+        use rayon::prelude::*;
+
+        let mut array = Array::create_filled(10_000, 1u64);
+        array.par_add_all(2);
+        array.par_map_in_place(|value| value * 10);
+        assert_eq!(array.par_iter().sum::<u64>(), 300_000);
+        array.par_iter_mut().enumerate().for_each(|(index, value)| *value = index as u64);
+        assert_eq!(array.get(1234), 1234);
+        array.par_set_all(5);
+        assert_eq!(array.par_iter().filter(|value| **value == 5).count(), 10_000);
+
+        let table = Table::new(Array::create_filled(8 * 6, 0u32), 8, 6);
+        let mut sub_table = unsafe { table.sub_table(1, 2, 5, 3) };
+        sub_table.par_set_all(1);
+        sub_table.par_add_all(2);
+        sub_table.par_map_in_place(|value| value * 2);
+        sub_table.par_rows_mut().enumerate().for_each(|(y, mut row)| row[0] = y as u32);
+        assert_eq!(sub_table.par_rows().count(), 3);
+        assert_eq!(sub_table.par_rows().map(|row| row.iter().sum::<u32>()).sum::<u32>(), 4 * 6 * 3 + 3);
+        assert_eq!(table.get_row(1), vec![0; 8]);
+        assert_eq!(table.get_row(3), vec![0, 1, 6, 6, 6, 6, 0, 0]);
+        assert_eq!(table.get_row(5), vec![0; 8]);
+
+        // Tables whose rows are not contiguous
+        let transposed = Table::with_strides(Array::create_filled(12, 1u32), 0, 3, 4, 4, 1);
+        transposed.par_add_all(2);
+        transposed.par_map_in_place(|value| value * 3);
+        assert_eq!(transposed.get_row(3), vec![9, 9, 9]);
+        let mut transposed_sub_table = unsafe { transposed.sub_table(1, 1, 2, 3) };
+        transposed_sub_table.par_rows_mut().enumerate().for_each(|(y, mut row)| {
+            row[0] = y as u32;
+            row[1] += 1;
+        });
+        let rows: Vec<Vec<u32>> = transposed.par_rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows, vec![vec![9, 9, 9], vec![9, 0, 10], vec![9, 1, 10], vec![9, 2, 10]]);
+        assert_eq!(transposed.get_array().as_slice(), &[9, 9, 9, 9, 9, 0, 1, 2, 9, 10, 10, 10]);
+
+        let morton = Table::with_layout(Array::create_filled(64, 0u32), 8, 8, crate::TableLayout::Morton);
+        let morton_sub_table = unsafe { morton.sub_table(3, 2, 4, 5) };
+        morton_sub_table.par_add_all(4);
+        assert_eq!(morton.get_row(2), vec![0, 0, 0, 4, 4, 4, 4, 0]);
+        assert_eq!(morton.get_column(6), vec![0, 0, 4, 4, 4, 4, 4, 0]);
+        assert_eq!(morton_sub_table.par_rows().map(|row| row.iter().sum::<u32>()).sum::<u32>(), 4 * 4 * 5);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mapped_arrays(){
+        use crate::MapMode;
+        use std::fs::{File, OpenOptions, remove_file};
+
+        let path = std::env::temp_dir().join(format!("memory_structures_mapping_{}.bin", std::process::id()));
+        {
+            let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path).unwrap();
+            let array: Array<u32> = unsafe { Array::map_file_with_size(&file, 12) }.unwrap();
+            assert!(array.is_mapped());
+            assert_eq!(array.len(), 12);
+            assert_eq!(array.get(11), 0);
+
+            let table = unsafe { Table::new(array.sharing_copy(), 4, 3) };
+            table.draw_line(0, 1, 3, 1, 7);
+            table.set(3, 2, 42);
+            array.flush().unwrap();
+        }
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 48);
+        {
+            let file = File::open(&path).unwrap();
+            let array: Array<u32> = unsafe { Array::map_file(&file, MapMode::CopyOnWrite) }.unwrap();
+            let table = Table::new(array, 4, 3);
+            assert_eq!(table.get_row(1), vec![7; 4]);
+            assert_eq!(table.get(3, 2), 42);
+
+            // Modifications of copy-on-write mappings must not reach the file
+            table.set_all(1);
+            assert_eq!(table.get(3, 2), 1);
+        }
+        {
+            let file = File::open(&path).unwrap();
+            let array: Array<u32> = unsafe { Array::map_file(&file, MapMode::CopyOnWrite) }.unwrap();
+            assert_eq!(array.get(11), 42);
+            assert!(unsafe { Array::<u32>::map_file(&file, MapMode::ReadWrite) }.is_err());
+            assert!(!unsafe { array.sharing_copy() }.is_mapped());
+            assert!(unsafe { Array::<()>::map_file(&file, MapMode::CopyOnWrite) }.is_err());
+            assert!(unsafe { Array::<()>::map_file_with_size(&file, 5) }.is_err());
+        }
+        remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_aligned_arrays(){
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        for alignment in [1, 8, 32, 64, 4096].iter() {
+            let array = Array::create_filled_aligned(100, *alignment, 3u8);
+            assert!(array.is_aligned_to(*alignment));
+            assert_eq!(array.get(99), 3);
+        }
+        let table = Table::new(Array::create_filled_aligned(64, 64, 1.5f32), 8, 8);
+        table.draw_line(0, 0, 7, 0, 1.0);
+        assert_eq!(table.get(7, 0), 2.5);
+
+        assert_eq!(Array::<u32>::try_create_garbage_aligned(10, 2).err(), Some(MemoryStructuresError::InvalidAlignment { alignment: 2, minimum_alignment: 4 }));
+        assert_eq!(Array::<u32>::try_create_garbage_aligned(10, 48).err(), Some(MemoryStructuresError::InvalidAlignment { alignment: 48, minimum_alignment: 4 }));
+        assert_eq!(Array::<u32>::try_create_garbage_aligned(0, 64).err(), Some(MemoryStructuresError::ZeroSize));
+
+        struct CountingAllocator {
+            live_bytes: Arc<AtomicUsize>
+        }
+
+        unsafe impl GlobalAlloc for CountingAllocator {
+
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                self.live_bytes.fetch_add(layout.size(), Ordering::SeqCst);
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout){
+                self.live_bytes.fetch_sub(layout.size(), Ordering::SeqCst);
+                System.dealloc(pointer, layout)
+            }
+        }
+
+        let live_bytes = Arc::new(AtomicUsize::new(0));
+        {
+            let array: Array<u64> = Array::create_garbage_in(50, 32, CountingAllocator { live_bytes: live_bytes.clone() });
+            assert!(array.is_aligned_to(32));
+            assert_eq!(live_bytes.load(Ordering::SeqCst), 400);
+            array.set_all(8);
+            assert_eq!(array.create_vector(48, 2), vec![8, 8]);
+        }
+        assert_eq!(live_bytes.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_simd_against_scalar(){
+        use crate::simd::SimdPath;
+        use crate::utility::Saturating;
+        use std::ops::AddAssign;
+        use std::fmt::Debug;
+
+        // Use sizes that are not multiples of any vector size, and unaligned sub arrays. The reference
+        // results are computed with plain loops over a Vec.
+        fn compare<T: Saturating + AddAssign + Copy + PartialEq + Debug>(path: SimdPath, initial: T, amount: T){
+            let size = 203;
+            let mut expected = vec![initial; size];
+            let array = path.force(|| {
+                let array = Array::create_filled(size, initial);
+                array.saturating_add_all(amount);
+                array.saturating_add_some(3, 150, amount);
+                array.set_some(17, 41, initial);
+                array.saturating_add_some(size + 10, 0, amount);
+                unsafe {
+                    array.sharing_sub_array(1, 100).saturating_add_all(amount);
+                }
+                array
+            });
+            expected.iter_mut().for_each(|element| *element = element.saturating_add(amount));
+            expected[3..153].iter_mut().for_each(|element| *element = element.saturating_add(amount));
+            expected[17..58].iter_mut().for_each(|element| *element = initial);
+            expected[1..101].iter_mut().for_each(|element| *element = element.saturating_add(amount));
+            assert_eq!(expected, array.create_vector(0, size), "{:?}", path);
+        }
+
+        fn compare_add<T: AddAssign + Copy + PartialEq + Debug>(path: SimdPath, initial: T, amount: T){
+            let size = 203;
+            let mut expected = vec![initial; size];
+            let array = path.force(|| {
+                let array = Array::create_filled(size, initial);
+                array.add_all(amount);
+                array.add_some(5, 190, amount);
+                array.set_some(60, 3, amount);
+                array.set_some(size + 10, 0, amount);
+                array.add_some(size + 10, 0, amount);
+                unsafe {
+                    array.sharing_sub_array(7, 150).add_unchecked_some(1, 140, amount);
+                }
+                array
+            });
+            expected.iter_mut().for_each(|element| *element += amount);
+            expected[5..195].iter_mut().for_each(|element| *element += amount);
+            expected[60..63].iter_mut().for_each(|element| *element = amount);
+            expected[8..148].iter_mut().for_each(|element| *element += amount);
+            assert_eq!(expected, array.create_vector(0, size), "{:?}", path);
+        }
+
+        let paths = SimdPath::supported();
+        assert!(paths.contains(&SimdPath::Scalar));
+        for path in paths {
+            compare(path, 100u8, 60u8);
+            compare(path, -100i8, -20i8);
+            compare(path, 60_000u16, 1_000u16);
+            compare(path, 30_000i16, 1_000i16);
+            compare(path, u32::MAX - 100, 40u32);
+            compare(path, i64::MIN + 5, -2i64);
+            compare(path, u128::MAX - 3, 2u128);
+
+            compare_add(path, 1u8, 2u8);
+            compare_add(path, -1i8, 2i8);
+            compare_add(path, 300u16, 7u16);
+            compare_add(path, -300i16, 7i16);
+            compare_add(path, 70_000u32, 3u32);
+            compare_add(path, -70_000i32, 3i32);
+            compare_add(path, 1u64 << 40, 9u64);
+            compare_add(path, -(1i64 << 40), 9i64);
+            compare_add(path, 0.25f32, 1.5f32);
+            compare_add(path, -0.25f64, 1.5f64);
+            compare_add(path, 5usize, 1usize);
+
+            // The vectorized integer additions wrap around
+            let array = path.force(|| {
+                let array = Array::create_filled(70, 250u8);
+                array.add_all(10);
+                array
+            });
+            assert_eq!(array.create_vector(0, 70), vec![4; 70]);
+        }
+
+        // Without a forced path, integer overflow behaves like += does
+        if cfg!(debug_assertions) {
+            catch_unwind(|| Array::create_filled(70, 250u8).add_all(10)).unwrap_err();
+        } else {
+            let array = Array::create_filled(70, 250u8);
+            array.add_all(10);
+            assert_eq!(array.create_vector(0, 70), vec![4; 70]);
+        }
+
+        // Other element types use the scalar loops
+        let array = Array::create_filled(9, (1u8, 2.5f32));
+        array.set_some(2, 3, (4, 0.5));
+        assert_eq!(array.get(1), (1, 2.5));
+        assert_eq!(array.get(4), (4, 0.5));
+        assert_eq!(array.get(5), (1, 2.5));
+
+        let array = Array::create_filled(10, 0u32);
+        catch_unwind(|| {
+            array.add_some(5, 6, 1);
+        }).unwrap_err();
+    }
+
+    #[test]
+    fn test_reductions(){
+        let array = Array::from_vec(vec![4u8, 200, 7, 1, 90, 200, 1]);
+        assert_eq!(array.saturating_sum(), 255);
+        assert_eq!(array.saturating_sum_some(2, 3), 98);
+        assert_eq!(array.sum_some(0, 1), 4);
+        assert_eq!(array.sum_some(3, 0), 0);
+        assert_eq!(array.try_sum_some(20, 0), Ok(0));
+        assert_eq!(array.try_saturating_sum_some(20, 0), Ok(0));
+        assert_eq!(array.try_inclusive_scan_some(20, 0), Ok(()));
+        assert_eq!(array.try_saturating_exclusive_scan_some(20, 0), Ok(()));
+        assert!(array.try_sum_some(5, 3).is_err());
+        assert_eq!(array.min(), 1);
+        assert_eq!(array.max(), 200);
+        assert_eq!(array.argmin(), 3);
+        assert_eq!(array.argmax(), 1);
+        assert_eq!(array.argmax_some(2, 5), 5);
+        assert_eq!(array.argmin_some(4, 3), 6);
+        assert_eq!(array.min_some(0, 3), 4);
+        assert_eq!(array.max_some(2, 3), 90);
+        assert!(array.try_min_some(2, 0).is_err());
+        assert!(array.try_argmax_some(6, 2).is_err());
+        assert!((array.mean() - 503.0 / 7.0).abs() < 1e-9);
+        assert_eq!(array.mean_some(3, 2), 45.5);
+
+        assert_eq!(array.saturating_inclusive_scan().as_slice(), &[4, 204, 211, 212, 255, 255, 255]);
+        assert_eq!(array.saturating_exclusive_scan().as_slice(), &[0, 4, 204, 211, 212, 255, 255]);
+        assert_eq!(array.get(1), 200);
+        array.saturating_exclusive_scan_some(2, 3);
+        assert_eq!(array.as_slice(), &[4, 200, 0, 7, 8, 200, 1]);
+        array.saturating_inclusive_scan_some(4, 3);
+        assert_eq!(array.as_slice(), &[4, 200, 0, 7, 8, 208, 209]);
+        assert!(array.try_saturating_inclusive_scan_some(6, 2).is_err());
+
+        let numbers = Array::from_vec((1..=10).collect::<Vec<i64>>());
+        assert_eq!(numbers.sum(), 55);
+        assert_eq!(numbers.inclusive_scan().as_slice(), &[1, 3, 6, 10, 15, 21, 28, 36, 45, 55]);
+        assert_eq!(numbers.exclusive_scan().as_slice(), &[0, 1, 3, 6, 10, 15, 21, 28, 36, 45]);
+        numbers.inclusive_scan_some(7, 3);
+        assert_eq!(numbers.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 17, 27]);
+        numbers.exclusive_scan_some(0, 3);
+        assert_eq!(numbers.as_slice(), &[0, 1, 3, 4, 5, 6, 7, 8, 17, 27]);
+        numbers.inclusive_scan_in_place();
+        assert_eq!(numbers.get(9), 78);
+        numbers.exclusive_scan_in_place();
+        assert_eq!(numbers.get(1), 0);
+        assert!(catch_unwind(|| numbers.exclusive_scan_some(8, 3)).is_err());
+
+        let floats = Array::from_vec(vec![2.5f32, f32::NAN, -1.0, 7.0]);
+        assert_eq!(floats.argmin(), 2);
+        assert_eq!(floats.argmax(), 3);
+        assert_eq!(floats.sum_some(2, 2), 6.0);
+        assert!(floats.mean().is_nan());
+    }
+
+    #[test]
+    fn test_array_from_vec(){
+        let array = Array::from_vec(vec![1, 2, 3]);
+        assert_eq!(array.len(), 3);
+        array.add(2, 4);
+        assert_eq!(array.create_vector(0, 3), vec![1, 2, 7]);
+        assert_eq!(Array::<u8>::try_from_vec(Vec::new()).err(), Some(MemoryStructuresError::ZeroSize));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde(){
+        let array = Array::from_vec(vec![1u16, 2, 3, 4, 5]);
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(json, "[1,2,3,4,5]");
+        let sub_array = unsafe { array.sharing_sub_array(1, 3) };
+        assert_eq!(serde_json::to_string(&sub_array).unwrap(), "[2,3,4]");
+        let loaded: Array<u16> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.create_vector(0, 5), vec![1, 2, 3, 4, 5]);
+        assert!(serde_json::from_str::<Array<u16>>("[]").is_err());
+
+        let table = Table::new(Array::from_vec((0..12).collect()), 4, 3);
+        let sub_table = unsafe { table.sub_table(1, 1, 2, 2) };
+        let json = serde_json::to_string(&sub_table).unwrap();
+        assert_eq!(json, r#"{"width":2,"height":2,"elements":[5,6,9,10]}"#);
+        let loaded: Table<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_width(), 2);
+        assert_eq!(loaded.get_row(1), vec![9, 10]);
+        assert!(serde_json::from_str::<Table<i32>>(r#"{"width":2,"height":2,"elements":[5,6,9]}"#).is_err());
+        assert!(serde_json::from_str::<Table<i32>>(r#"{"width":0,"height":2,"elements":[]}"#).is_err());
+
+        let bytes = bincode::serialize(&table).unwrap();
+        let loaded: Table<i32> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(loaded.get_height(), 3);
+        for y in 0..3 {
+            assert_eq!(loaded.get_row(y), table.get_row(y));
+        }
+    }
+
+    #[test]
+    fn test_snapshots(){
+        use crate::SnapshotError;
+
+        let array = Array::from_vec(vec![1.5f64, -2.0, 1e300]);
+        let mut bytes = Vec::new();
+        array.save_to(&mut bytes, true).unwrap();
+        assert_eq!(&bytes[0..4], b"MSSN");
+        assert_eq!(bytes.len(), 10 + 8 + 3 * 8 + 4);
+        let loaded = Array::<f64>::load_from(&bytes[..]).unwrap();
+        assert_eq!(loaded.create_vector(0, 3), vec![1.5, -2.0, 1e300]);
+
+        // The wrong type or structure must be detected
+        match Array::<u64>::load_from(&bytes[..]) {
+            Err(SnapshotError::WrongElementType { expected: 7, found: 12 }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        match Table::<f64>::load_from(&bytes[..]) {
+            Err(SnapshotError::WrongDimensions { expected: 2, found: 1 }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+
+        // Corruption must be detected by the checksum
+        let mut corrupted = bytes.clone();
+        corrupted[20] ^= 1;
+        match Array::<f64>::load_from(&corrupted[..]) {
+            Err(SnapshotError::ChecksumMismatch { .. }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        match Array::<f64>::load_from(&bytes[..30]) {
+            Err(SnapshotError::Io(_)) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        corrupted[0] = b'X';
+        assert!(matches!(Array::<f64>::load_from(&corrupted[..]), Err(SnapshotError::InvalidMagic(_))));
+
+        // Snapshots of sub tables only contain the visible elements
+        let table = Table::new(Array::from_vec((0..20_000u32).collect()), 200, 100);
+        let sub_table = unsafe { table.sub_table(10, 20, 150, 60) };
+        let mut bytes = Vec::new();
+        sub_table.save_to(&mut bytes, false).unwrap();
+        assert_eq!(bytes.len(), 10 + 16 + 150 * 60 * 4);
+        let loaded = Table::<u32>::load_from(&bytes[..]).unwrap();
+        assert_eq!((loaded.get_width(), loaded.get_height()), (150, 60));
+        assert_eq!(loaded.get(0, 0), 20 * 200 + 10);
+        assert_eq!(loaded.get(149, 59), 79 * 200 + 159);
+
+        // Snapshots written by a machine with the other byte order can be read as well
+        let mut swapped = vec![b'M', b'S', b'S', b'N', 1, 0, 1, 3, 1, 1];
+        swapped.extend_from_slice(&2u64.to_be_bytes());
+        swapped.extend_from_slice(&0x1234u16.to_be_bytes());
+        swapped.extend_from_slice(&0xABCDu16.to_be_bytes());
+        let mut checksum_data = swapped.clone();
+        let mut flagless = checksum_data.clone();
+        flagless[9] = 0;
+        let loaded = Array::<u16>::load_from(&flagless[..]).unwrap();
+        assert_eq!(loaded.create_vector(0, 2), vec![0x1234, 0xABCD]);
+        checksum_data.extend_from_slice(&0u32.to_be_bytes());
+        assert!(matches!(Array::<u16>::load_from(&checksum_data[..]), Err(SnapshotError::ChecksumMismatch { .. })));
+
+        // A corrupted header must not cause a huge allocation
+        let mut huge = vec![b'M', b'S', b'S', b'N', 1, 0, 0, 3, 2, 0];
+        huge.extend_from_slice(&(1u64 << 31).to_le_bytes());
+        huge.extend_from_slice(&(1u64 << 31).to_le_bytes());
+        huge.extend_from_slice(&[1, 2, 3, 4]);
+        assert!(matches!(Table::<u16>::load_from(&huge[..]), Err(SnapshotError::Io(_))));
+        huge[10..18].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(Table::<u16>::load_from(&huge[..]), Err(SnapshotError::InvalidStructure(MemoryStructuresError::Overflow))));
+        huge[10..18].copy_from_slice(&0u64.to_le_bytes());
+        assert!(matches!(Table::<u16>::load_from(&huge[..]), Err(SnapshotError::InvalidStructure(_))));
+    }
+
+    #[test]
+    fn test_csv(){
+        use crate::{CsvError, CsvFormat};
+
+        let table = Table::new(Array::from_vec(vec![0.5f64, 1.0, -2.25, 3.0, 4.0, 5.5]), 3, 2);
+        assert_eq!(table.to_csv_string(&CsvFormat::csv()), "0.5,1,-2.25\n3,4,5.5\n");
+        let tsv = table.to_csv_string(&CsvFormat::tsv().with_header(true));
+        assert_eq!(tsv, "0\t1\t2\n0.5\t1\t-2.25\n3\t4\t5.5\n");
+
+        let loaded = Table::<f64>::from_csv_str(&tsv, &CsvFormat::tsv().with_header(true)).unwrap();
+        assert_eq!((loaded.get_width(), loaded.get_height()), (3, 2));
+        assert_eq!(loaded.get_row(0), vec![0.5, 1.0, -2.25]);
+        assert_eq!(loaded.get_row(1), vec![3.0, 4.0, 5.5]);
+
+        let loaded = Table::<u32>::from_csv_str("1, 2\r\n\n3 ,4\n5,6\n", &CsvFormat::csv()).unwrap();
+        assert_eq!((loaded.get_width(), loaded.get_height()), (2, 3));
+        assert_eq!(loaded.get_column(1), vec![2, 4, 6]);
+
+        let sub_table = unsafe { loaded.sub_table(1, 1, 1, 2) };
+        assert_eq!(sub_table.to_csv_string(&CsvFormat::csv().with_header(true)), "0\n4\n6\n");
+
+        match Table::<u32>::from_csv_str("1,2\n3,x\n", &CsvFormat::csv()) {
+            Err(CsvError::InvalidValue { line: 2, column: 2, value }) => assert_eq!(value, "x"),
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        match Table::<u32>::from_csv_str("1,2\n3\n", &CsvFormat::csv()) {
+            Err(CsvError::RaggedRow { line: 2, expected: 2, found: 1 }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        match Table::<u32>::from_csv_str("0,2\n3,4\n", &CsvFormat::csv().with_header(true)) {
+            Err(CsvError::InvalidHeader { line: 1, column: 2, .. }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        match Table::<u32>::from_csv_str("0,1\n3,4,5\n", &CsvFormat::csv().with_header(true)) {
+            Err(CsvError::RaggedRow { line: 2, expected: 2, found: 3 }) => {},
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        };
+        assert!(matches!(Table::<u32>::from_csv_str("\n\n", &CsvFormat::csv()), Err(CsvError::Empty)));
+    }
+
+    #[test]
+    fn test_standard_traits(){
+        use std::collections::HashSet;
+
+        let array = Array::from_vec((0..100).collect::<Vec<u32>>());
+        let sub_array = unsafe { array.sharing_sub_array(10, 3) };
+        let clone = sub_array.clone();
+        clone.set(0, 1000);
+        assert_eq!(array.get(10), 10);
+        assert_eq!(clone, Array::from_vec(vec![1000, 11, 12]));
+        assert_ne!(clone, sub_array);
+        assert_eq!(format!("{:?}", sub_array), "Array { len: 3, elements: [10, 11, 12] }");
+        let debug = format!("{:?}", array);
+        assert!(debug.ends_with("29, 30, 31, ... 68 more] }"));
+
+        let table = Table::new(Array::from_vec(vec![1, 20, 3, -4, 5, 600, 7, 8, 9]), 3, 3);
+        assert_eq!(format!("{}", table), " 1 20   3\n-4  5 600\n 7  8   9");
+        let sub_table = unsafe { table.sub_table(1, 1, 2, 2) };
+        assert_eq!(format!("{:?}", sub_table), "Table { width: 2, height: 2, rows: [[5, 600], [8, 9]] }");
+
+        let cloned_table = sub_table.clone();
+        assert_eq!(cloned_table, sub_table);
+        assert_eq!(cloned_table.get_row_stride(), 2);
+        cloned_table.set(0, 0, 50);
+        assert_eq!(table.get(1, 1), 5);
+        assert_ne!(cloned_table, sub_table);
+
+        let mut set = HashSet::new();
+        set.insert(sub_table.clone());
+        assert!(set.contains(&Table::new(Array::from_vec(vec![5, 600, 8, 9]), 2, 2)));
+        assert!(!set.contains(&Table::new(Array::from_vec(vec![5, 600, 8, 9]), 4, 1)));
+
+        let float_table = Table::new(Array::from_vec(vec![1.0, 0.125, -10.5, 2.0]), 2, 2);
+        assert_eq!(format!("{:.2}", float_table), "  1.00 0.12\n-10.50 2.00");
+    }
+
+    #[test]
+    fn test_volumes(){
+        use crate::{Axis, Graphics3D, Volume};
+
+        let volume = Volume::new(Array::create_filled(4 * 3 * 2, 0), 4, 3, 2);
+        for z in 0..2 {
+            for y in 0..3 {
+                for x in 0..4 {
+                    volume.set(x, y, z, x + 10 * y + 100 * z);
+                }
+            }
+        }
+        assert_eq!(volume.index_for(3, 2, 1), 23);
+        assert_eq!(volume.try_get(4, 0, 0), Err(MemoryStructuresError::VolumeCoordinatesOutOfBounds { x: 4, y: 0, z: 0, width: 4, height: 3, depth: 2 }));
+
+        unsafe {
+            let slice_x = volume.slice(Axis::X, 1);
+            assert_eq!((slice_x.get_width(), slice_x.get_height()), (3, 2));
+            assert_eq!(slice_x.get_row(1), vec![101, 111, 121]);
+            let slice_y = volume.slice(Axis::Y, 2);
+            assert_eq!(slice_y.get_column(3), vec![23, 123]);
+            let slice_z = volume.slice(Axis::Z, 1);
+            assert_eq!(slice_z.get_row(2), vec![120, 121, 122, 123]);
+
+            // Table operations on a slice with a column stride that is not 1
+            slice_x.draw_line(0, 0, 2, 0, 1000);
+            slice_x.add_row(1, 5000);
+            assert_eq!(volume.get(1, 0, 0), 1001);
+            assert_eq!(volume.get(1, 2, 0), 1021);
+            assert_eq!(volume.get(1, 2, 1), 5121);
+            assert_eq!(volume.get(2, 2, 1), 122);
+            assert_eq!(slice_x.clone().get_row(0), vec![1001, 1011, 1021]);
+            assert!(volume.try_slice(Axis::Z, 2).is_err());
+        }
+
+        volume.set_all(0);
+        volume.set_plane(Axis::Y, 1, 7);
+        volume.add_plane(Axis::X, 0, 1);
+        volume.add_box(1, 0, 0, 2, 2, 0, 10);
+        volume.set_box(3, 2, 1, 3, 2, 1, 99);
+        unsafe {
+            assert_eq!(volume.slice(Axis::Z, 0).get_row(0), vec![1, 10, 10, 0]);
+            assert_eq!(volume.slice(Axis::Z, 0).get_row(1), vec![8, 17, 17, 7]);
+            assert_eq!(volume.slice(Axis::Z, 1).get_row(1), vec![8, 7, 7, 7]);
+            assert_eq!(volume.slice(Axis::Z, 1).get_row(2), vec![1, 0, 0, 99]);
+        }
+        catch_unwind(|| {
+            volume.set_box(0, 0, 0, 4, 0, 0, 1);
+        }).unwrap_err();
+
+        // 3D lines visit every point exactly once and skip points outside the volume
+        let volume = Volume::new(Array::create_filled(5 * 5 * 5, 0u32), 5, 5, 5);
+        volume.draw_line(0, 0, 0, 4, 4, 4, 1);
+        volume.draw_line(4, 0, 4, 0, 2, 0, 1);
+        volume.draw_line(2, 2, 2, 2, 2, 100, 10);
+        volume.draw_line(10, 0, 0, 20, 0, 0, 1);
+        assert_eq!(volume.get(0, 0, 0), 1);
+        assert_eq!(volume.get(3, 3, 3), 1);
+        assert_eq!(volume.get(2, 1, 2), 1);
+        assert_eq!(volume.get(0, 2, 0), 1);
+        assert_eq!(volume.get(2, 2, 2), 11);
+        assert_eq!(volume.get(2, 2, 4), 10);
+        let mut sum = 0;
+        for z in 0..5 {
+            for y in 0..5 {
+                for x in 0..5 {
+                    sum += volume.get(x, y, z);
+                }
+            }
+        }
+        assert_eq!(sum, 5 + 5 + 3 * 10);
+
+        // Lines that start far outside the volume only visit the points within bounds
+        volume.set_box(0, 0, 0, 4, 4, 4, 0);
+        volume.draw_line(usize::MAX, 0, 0, 0, 0, 0, 1);
+        assert_eq!((0..5).map(|x| volume.get(x, 0, 0)).collect::<Vec<_>>(), vec![1; 5]);
+        volume.draw_line(usize::MAX, usize::MAX / 2, 3, 0, 0, 0, 1);
+        assert_eq!(volume.get(0, 0, 0), 2);
+        volume.draw_line(usize::MAX, usize::MAX, usize::MAX, usize::MAX - 5, 3, 7, 1);
+
+        // Clipping must give the same points as drawing the whole line in a larger volume
+        let large = Volume::new(Array::create_filled(30 * 30 * 30, 0u32), 30, 30, 30);
+        let small = Volume::new(Array::create_filled(10 * 10 * 10, 0u32), 10, 10, 10);
+        let lines = [
+            (25, 3, 0, 0, 8, 29), (0, 29, 4, 29, 0, 5), (12, 1, 2, 3, 27, 9), (7, 7, 7, 7, 7, 7),
+            (29, 29, 29, 0, 0, 0), (2, 15, 9, 8, 0, 1), (9, 2, 28, 4, 5, 0), (28, 9, 3, 1, 4, 6)
+        ];
+        for (x1, y1, z1, x2, y2, z2) in lines.iter().copied() {
+            large.draw_line(x1, y1, z1, x2, y2, z2, 1);
+            small.draw_line(x1, y1, z1, x2, y2, z2, 1);
+        }
+        for z in 0..10 {
+            for y in 0..10 {
+                for x in 0..10 {
+                    assert_eq!(small.get(x, y, z), large.get(x, y, z), "({}, {}, {})", x, y, z);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nd_arrays(){
+        use crate::NdArray;
+
+        let tensor = NdArray::new(Array::from_vec((0..24).collect::<Vec<u32>>()), [2, 3, 4]);
+        assert_eq!(tensor.get_strides(), [12, 4, 1]);
+        assert_eq!(tensor.len(), 24);
+        assert_eq!(tensor.get([1, 2, 3]), 23);
+        assert_eq!(tensor.get([1, 0, 2]), 14);
+        assert!(tensor.try_get([2, 0, 0]).is_err());
+        assert_eq!(tensor.indices().count(), 24);
+        assert_eq!(tensor.indices().nth(5), Some([0, 1, 1]));
+        assert_eq!(tensor.iter().copied().collect::<Vec<u32>>(), (0..24).collect::<Vec<u32>>());
+
+        let view = unsafe { tensor.sharing_copy() }.into_slice([1..2, 0..3, 1..3]);
+        assert_eq!(view.get_shape(), [1, 3, 2]);
+        assert_eq!(view.iter().copied().collect::<Vec<u32>>(), vec![13, 14, 17, 18, 21, 22]);
+        view.add_all(100);
+        assert_eq!(tensor.get([1, 1, 2]), 118);
+        assert_eq!(tensor.get([1, 1, 3]), 19);
+
+        let transposed = unsafe { tensor.sharing_copy() }.into_transposed();
+        assert_eq!(transposed.get_shape(), [4, 3, 2]);
+        assert_eq!(transposed.get([3, 2, 0]), 11);
+        let permuted = unsafe { tensor.sharing_copy() }.into_permuted_axes([1, 0, 2]);
+        assert_eq!(permuted.get_shape(), [3, 2, 4]);
+        assert_eq!(permuted.get([2, 1, 0]), 20);
+        assert!(unsafe { tensor.sharing_copy() }.try_into_permuted_axes([0, 0, 1]).is_err());
+        assert!(unsafe { tensor.sharing_copy() }.try_into_slice([0..3, 0..1, 0..1]).is_err());
+
+        // Broadcasting a row vector and a column vector over a matrix
+        let matrix = NdArray::new(Array::create_filled(6, 0i64), [2, 3]);
+        let row = NdArray::new(Array::from_vec(vec![1, 2, 3]), [1, 3]);
+        let column = NdArray::new(Array::from_vec(vec![10, 20]), [2, 1]);
+        matrix.add_broadcast(&row);
+        matrix.add_broadcast(&column);
+        assert_eq!(matrix.iter().copied().collect::<Vec<i64>>(), vec![11, 12, 13, 21, 22, 23]);
+        matrix.assign_broadcast(&column);
+        assert_eq!(matrix.iter().copied().collect::<Vec<i64>>(), vec![10, 10, 10, 20, 20, 20]);
+        assert!(matrix.try_add_broadcast(&NdArray::new(Array::from_vec(vec![1, 2]), [1, 2])).is_err());
+
+        // A row of a matrix can be added to the same matrix
+        let last_row = unsafe { matrix.sharing_copy() }.into_slice([1..2, 0..3]);
+        matrix.add_broadcast(&last_row);
+        assert_eq!(matrix.iter().copied().collect::<Vec<i64>>(), vec![30, 30, 30, 40, 40, 40]);
+        matrix.fill(-1);
+        assert_eq!(matrix.get([1, 2]), -1);
+
+        let broadcast = row.into_broadcast([4, 3]);
+        assert_eq!(broadcast.get_strides(), [0, 1]);
+        assert_eq!(broadcast.get([3, 2]), 3);
+    }
+
+    #[test]
+    fn test_table_layouts(){
+        use crate::TableLayout;
+
+        assert_eq!(TableLayout::RowMajor.required_size(5, 3), 15);
+        assert_eq!(TableLayout::Tiled { tile_width: 4, tile_height: 2 }.required_size(5, 3), 32);
+        assert_eq!(TableLayout::Morton.required_size(4, 4), 16);
+        assert_eq!(TableLayout::Morton.required_size(3, 2), 7);
+        assert!(TableLayout::Tiled { tile_width: 0, tile_height: 2 }.try_required_size(5, 3).is_err());
+
+        let column_major = Table::with_layout(Array::create_filled(6, 0u32), 3, 2, TableLayout::ColumnMajor);
+        assert_eq!(column_major.index_for(2, 1), 5);
+        assert_eq!(column_major.index_for(1, 0), 2);
+        assert!(column_major.has_contiguous_columns());
+        let morton = Table::with_layout(Array::create_filled(16, 0u32), 4, 4, TableLayout::Morton);
+        assert_eq!(morton.index_for(1, 0), 1);
+        assert_eq!(morton.index_for(0, 1), 2);
+        assert_eq!(morton.index_for(2, 0), 4);
+        assert_eq!(morton.index_for(3, 3), 15);
+        assert_eq!(morton.get_indexing(), crate::TableIndexing::Morton { origin_x: 0, origin_y: 0 });
+        catch_unwind(|| morton.get_column_stride()).unwrap_err();
+        assert_eq!(column_major.get_column_stride(), 2);
+        assert_eq!(column_major.get_indexing(), crate::TableIndexing::Strided { column_stride: 2, row_stride: 1 });
+        let tiled = Table::with_layout(Array::create_filled(32, 0u32), 5, 3, TableLayout::Tiled { tile_width: 4, tile_height: 2 });
+        assert_eq!(tiled.index_for(1, 1), 5);
+        assert_eq!(tiled.index_for(4, 0), 8);
+        assert_eq!(tiled.index_for(0, 2), 16);
+        assert!(Table::try_with_layout(Array::create_filled(31, 0u32), 5, 3, TableLayout::Tiled { tile_width: 4, tile_height: 2 }).is_err());
+
+        // Every layout must behave exactly like the row-major layout
+        let layouts = [
+            TableLayout::RowMajor, TableLayout::ColumnMajor, TableLayout::Morton,
+            TableLayout::Tiled { tile_width: 3, tile_height: 2 }, TableLayout::Tiled { tile_width: 1, tile_height: 4 }
+        ];
+        let create = |layout: TableLayout| Table::with_layout(Array::create_filled(layout.required_size(7, 5), 0i32), 7, 5, layout);
+        let expected = create(TableLayout::RowMajor);
+        let operate = |table: &Table<i32>| {
+            table.set_all(1);
+            table.add_unchecked_rect(1, 1, 5, 3, 10);
+            table.draw_line(0, 4, 6, 0, 100);
+            table.set_row(2, 7);
+            table.add_column(3, 1000);
+            table.add_row(4, 5);
+            table.set_column(6, -1);
+            table.swap_rows(0, 3);
+            table.copy_column(1, 5);
+            let sub_table = unsafe { table.sub_table(2, 1, 4, 3) };
+            sub_table.add_unchecked_rect(1, 0, 3, 2, 20000);
+            sub_table.set_row(1, 3);
+            let nested = unsafe { sub_table.sub_table(1, 1, 2, 2) };
+            nested.add_unchecked_rect(0, 0, 1, 1, -50);
+            nested.set_column(0, 9);
+        };
+        operate(&expected);
+        for layout in layouts {
+            let table = create(layout);
+            operate(&table);
+            assert_eq!(table, expected, "layout {:?}", layout);
+            assert_eq!(format!("{}", table), format!("{}", expected));
+        }
+    }
+
+    #[test]
+    fn test_summed_area_tables(){
+        use crate::{FenwickTable, SummedAreaTable};
+
+        let table = Table::new(Array::from_vec((0..20).collect::<Vec<u32>>()), 5, 4);
+        let brute_force = |min_x: usize, min_y: usize, max_x: usize, max_y: usize| {
+            let mut sum = 0;
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    sum += table.get(x, y);
+                }
+            }
+            sum
+        };
+        let check = |sums: &dyn Fn(usize, usize, usize, usize) -> u32| {
+            for min_y in 0..4 {
+                for max_y in min_y..4 {
+                    for min_x in 0..5 {
+                        for max_x in min_x..5 {
+                            assert_eq!(sums(min_x, min_y, max_x, max_y), brute_force(min_x, min_y, max_x, max_y));
+                        }
+                    }
+                }
+            }
+        };
+
+        let summed_area_table = SummedAreaTable::new(&table);
+        assert_eq!(summed_area_table.rect_sum(0, 0, 4, 3), 190);
+        assert_eq!(summed_area_table.rect_sum(2, 1, 3, 2), 7 + 8 + 12 + 13);
+        check(&|min_x, min_y, max_x, max_y| summed_area_table.rect_sum(min_x, min_y, max_x, max_y));
+        assert!(summed_area_table.try_rect_sum(2, 0, 1, 0).is_err());
+        assert!(summed_area_table.try_rect_sum(0, 0, 5, 0).is_err());
+
+        table.add_unchecked_rect(2, 1, 4, 2, 100);
+        table.draw_line(3, 1, 4, 3, 7);
+        summed_area_table.rebuild_region(&table, 2, 1);
+        check(&|min_x, min_y, max_x, max_y| summed_area_table.rect_sum(min_x, min_y, max_x, max_y));
+        let other_table = Table::new(Array::create_filled(6, 0u32), 3, 2);
+        assert!(summed_area_table.try_rebuild_region(&other_table, 0, 0).is_err());
+        assert!(summed_area_table.try_rebuild_region(&table, 5, 0).is_err());
+
+        // The sums of small integer types can be stored in a wider type
+        let bytes = Table::new(Array::create_filled(300 * 200, 255u8), 300, 200);
+        let widened = SummedAreaTable::<u64>::new_widening(&bytes);
+        assert_eq!(widened.rect_sum(0, 0, 299, 199), 255 * 300 * 200);
+        bytes.set(299, 199, 0);
+        widened.rebuild_region(&bytes, 299, 199);
+        assert_eq!(widened.rect_sum(200, 100, 299, 199), 255 * 100 * 100 - 255);
+
+        let fenwick_table = FenwickTable::from_table(&table);
+        check(&|min_x, min_y, max_x, max_y| fenwick_table.rect_sum(min_x, min_y, max_x, max_y));
+        table.add_unchecked(3, 2, 50);
+        fenwick_table.add(3, 2, 50);
+        table.draw_line(0, 0, 4, 3, 1);
+        fenwick_table.draw_line(0, 0, 4, 3, 1);
+        check(&|min_x, min_y, max_x, max_y| fenwick_table.rect_sum(min_x, min_y, max_x, max_y));
+        assert_eq!(fenwick_table.get(3, 2), table.get(3, 2));
+        assert!(fenwick_table.try_add(5, 0, 1).is_err());
+
+        let empty = FenwickTable::<i64>::new(3, 3);
+        empty.add(1, 1, -5);
+        assert_eq!(empty.rect_sum(0, 0, 2, 2), -5);
+        assert_eq!(empty.rect_sum(2, 0, 2, 2), 0);
+    }
+
+    #[test]
+    fn test_convolution(){
+        use crate::{EdgeMode, kernels};
+
+        let table = Table::new(Array::from_vec(vec![
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0
+        ]), 3, 2);
+        let kernel = Table::new(Array::from_vec(vec![0.0, 0.0, 1.0]), 3, 1);
+        // The kernel is flipped, so a kernel whose last element is 1 shifts the table to the right
+        assert_eq!(table.convolve(&kernel, EdgeMode::Clamp).get_row(0), vec![1.0, 1.0, 2.0]);
+        assert_eq!(table.convolve(&kernel, EdgeMode::Wrap).get_row(0), vec![3.0, 1.0, 2.0]);
+        assert_eq!(table.convolve(&kernel, EdgeMode::Mirror).get_row(0), vec![2.0, 1.0, 2.0]);
+        assert_eq!(table.convolve(&kernel, EdgeMode::Zero).get_row(1), vec![0.0, 4.0, 5.0]);
+        assert_eq!(table.convolve_rows(&Array::from_vec(vec![0.0, 0.0, 1.0]), EdgeMode::Zero).get_row(1), vec![0.0, 4.0, 5.0]);
+        let vertical = Table::new(Array::from_vec(vec![1i8, 0, 0]), 1, 3);
+        assert_eq!(table.convolve(&vertical, EdgeMode::Zero).get_row(0), vec![4.0, 5.0, 6.0]);
+        assert_eq!(table.convolve(&vertical, EdgeMode::Zero).get_row(1), vec![0.0, 0.0, 0.0]);
+        assert_eq!(table.convolve_columns(&Array::from_vec(vec![1.0, 0.0, 0.0]), EdgeMode::Zero).get_row(0), vec![4.0, 5.0, 6.0]);
+        assert_eq!(table.convolve(&vertical, EdgeMode::Wrap).get_row(1), vec![1.0, 2.0, 3.0]);
+
+        // Kernels with an even size are flipped around their center as well
+        let even = Table::new(Array::from_vec(vec![1.0, 0.0]), 2, 1);
+        assert_eq!(table.convolve(&even, EdgeMode::Zero).get_row(0), vec![2.0, 3.0, 0.0]);
+        assert_eq!(table.convolve_rows(&Array::from_vec(vec![1.0, 0.0]), EdgeMode::Zero).get_row(0), vec![2.0, 3.0, 0.0]);
+
+        // The separable convolutions must match the equivalent 2D convolution for every edge mode
+        let heatmap = Table::new(Array::create_filled(9 * 7, 0.0f64), 9, 7);
+        heatmap.draw_line(0, 0, 8, 6, 3.0);
+        heatmap.add_unchecked_rect(2, 4, 5, 6, 1.5);
+        for edge_mode in [EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror, EdgeMode::Zero] {
+            let blurred = heatmap.gaussian_blur(1.2, edge_mode);
+            let expected = heatmap.convolve(&kernels::gaussian::<f64>(1.2), edge_mode);
+            let box_blurred = heatmap.convolve_rows(&kernels::box_filter_1d::<f64>(3), edge_mode);
+            let destination = Table::new(Array::create_filled(9 * 7, 0.0), 9, 7);
+            box_blurred.convolve_columns_into(&kernels::box_filter_1d::<f64>(3), edge_mode, &destination);
+            let box_expected = heatmap.convolve(&kernels::box_filter::<f64>(3, 3), edge_mode);
+            for y in 0..7 {
+                for x in 0..9 {
+                    assert!((blurred.get(x, y) - expected.get(x, y)).abs() < 1e-9);
+                    assert!((destination.get(x, y) - box_expected.get(x, y)).abs() < 1e-9);
+                }
+            }
+        }
+        let total: f64 = (0..7).map(|y| heatmap.get_row(y).iter().sum::<f64>()).sum();
+        let blurred = heatmap.gaussian_blur(0.8, EdgeMode::Wrap);
+        let blurred_total: f64 = (0..7).map(|y| blurred.get_row(y).iter().sum::<f64>()).sum();
+        assert!((total - blurred_total).abs() < 1e-9);
+
+        let ramp = Table::new(Array::from_vec((0..16).map(|value| (value % 4) as f32 * 2.0 + (value / 4) as f32).collect()), 4, 4);
+        assert_eq!(ramp.convolve(&kernels::sobel_x::<f32>(), EdgeMode::Clamp).get(1, 1), 16.0);
+        assert_eq!(ramp.convolve(&kernels::sobel_y::<f32>(), EdgeMode::Clamp).get(2, 2), 8.0);
+        assert_eq!(ramp.convolve(&kernels::laplacian::<f32>(), EdgeMode::Clamp).get(1, 2), 0.0);
+        assert_eq!(ramp.convolve(&kernels::laplacian::<f32>(), EdgeMode::Clamp).get(0, 0), 3.0);
+
+        let wrong_destination = Table::new(Array::create_filled(6, 0.0), 2, 3);
+        assert!(table.try_convolve_into(&kernel, EdgeMode::Zero, &wrong_destination).is_err());
+        assert!(table.try_convolve_rows_into(&Array::from_vec(vec![1.0]), EdgeMode::Zero, &wrong_destination).is_err());
+    }
+
+    #[test]
+    fn test_transforms(){
+        let table = Table::new(Array::from_vec((0..6).collect::<Vec<u32>>()), 3, 2);
+        let transposed = table.transpose();
+        assert_eq!((transposed.get_width(), transposed.get_height()), (2, 3));
+        assert_eq!(transposed.get_row(2), vec![2, 5]);
+        assert_eq!(table.rotate_90().get_row(0), vec![3, 0]);
+        assert_eq!(table.rotate_90().get_row(2), vec![5, 2]);
+        assert_eq!(table.rotate_270().get_row(0), vec![2, 5]);
+        assert_eq!(table.rotate_180().get_row(0), vec![5, 4, 3]);
+        assert_eq!(table.flip_horizontal().get_row(1), vec![5, 4, 3]);
+        assert_eq!(table.flip_vertical().get_row(0), vec![3, 4, 5]);
+        assert_eq!(table.rotate_90().rotate_90(), table.rotate_180());
+        assert_eq!(table.rotate_90().rotate_270(), table);
+        assert_eq!(table.crop(1, 0, 2, 2).get_column(1), vec![2, 5]);
+        assert!(table.try_crop(2, 1, 2, 1).is_err());
+        let padded = table.pad(1, 2, 0, 1, 9);
+        assert_eq!((padded.get_width(), padded.get_height()), (4, 5));
+        assert_eq!(padded.get_row(1), vec![9; 4]);
+        assert_eq!(padded.get_row(3), vec![9, 3, 4, 5]);
+        assert_eq!(padded.get_row(4), vec![9; 4]);
+        assert!(table.try_pad(usize::MAX, 0, 0, 0, 0).is_err());
+
+        table.rotate_180_in_place();
+        assert_eq!(table.get_row(0), vec![5, 4, 3]);
+        table.flip_horizontal_in_place();
+        table.flip_vertical_in_place();
+        assert_eq!(table.get_row(0), vec![0, 1, 2]);
+        assert!(table.try_transpose_in_place().is_err());
+        assert!(table.try_rotate_90_in_place().is_err());
+
+        // Large square tables cover multiple blocks, including partial blocks at the edges
+        let size = 71;
+        let square = Table::new(Array::from_vec((0..size * size).collect::<Vec<usize>>()), size, size);
+        let odd = Table::new(Array::from_vec((0..size * 5).collect::<Vec<usize>>()), size, 5);
+        odd.rotate_180_in_place();
+        assert_eq!(odd, Table::new(Array::from_vec((0..size * 5).rev().collect::<Vec<usize>>()), size, 5));
+        let expected = square.transpose();
+        square.transpose_in_place();
+        assert_eq!(square, expected);
+        assert_eq!(square.get(3, 70), 3 * size + 70);
+        let expected = square.rotate_90();
+        square.rotate_90_in_place();
+        assert_eq!(square, expected);
+        let expected = square.rotate_270();
+        square.rotate_270_in_place();
+        assert_eq!(square, expected);
+        let expected = square.rotate_180();
+        square.rotate_180_in_place();
+        assert_eq!(square, expected);
+
+        let morton = Table::with_layout(Array::create_filled(64, 0), 8, 8, crate::TableLayout::Morton);
+        morton.set_row(1, 3);
+        morton.rotate_90_in_place();
+        assert_eq!(morton.get_column(6), vec![3; 8]);
+        assert_eq!(morton.transpose().get_row(6), vec![3; 8]);
+    }
+
+    #[test]
+    fn test_resize(){
+        use crate::ResizeFilter;
+
+        let grid = Table::new(Array::from_vec(vec![
+            0u32, 10, 20, 30,
+            40, 50, 60, 70,
+            80, 90, 100, 110,
+            120, 130, 140, 150
+        ]), 4, 4);
+        let thumbnail = grid.resize(2, 2, ResizeFilter::Area);
+        assert_eq!(thumbnail.get_row(0), vec![25, 45]);
+        assert_eq!(thumbnail.get_row(1), vec![105, 125]);
+        assert_eq!(grid.resize(1, 1, ResizeFilter::Area).get(0, 0), 75);
+        assert_eq!(grid.resize(3, 1, ResizeFilter::Area).get_row(0), vec![63, 75, 88]);
+        assert_eq!(grid.resize(2, 2, ResizeFilter::Nearest).get_row(1), vec![130, 150]);
+        assert_eq!(grid.resize(2, 2, ResizeFilter::Bilinear).get_row(0), vec![25, 45]);
+        for filter in [ResizeFilter::Nearest, ResizeFilter::Bilinear, ResizeFilter::Bicubic, ResizeFilter::Area] {
+            assert_eq!(grid.resize(4, 4, filter), grid);
+        }
+        assert!(grid.try_resize(0, 3, ResizeFilter::Nearest).is_err());
+        assert_eq!(Err(MemoryStructuresError::Overflow), grid.try_resize(usize::MAX, 1, ResizeFilter::Nearest).map(|_| ()));
+
+        let coarse = Table::new(Array::from_vec(vec![0.0f64, 4.0, 8.0, 12.0]), 2, 2);
+        let nearest = coarse.resize(4, 4, ResizeFilter::Nearest);
+        assert_eq!(nearest.get_row(0), vec![0.0, 0.0, 4.0, 4.0]);
+        assert_eq!(nearest.get_row(3), vec![8.0, 8.0, 12.0, 12.0]);
+        let bilinear = coarse.resize(4, 4, ResizeFilter::Bilinear);
+        assert_eq!(bilinear.get_row(0), vec![0.0, 1.0, 3.0, 4.0]);
+        assert_eq!(bilinear.get_column(0), vec![0.0, 2.0, 6.0, 8.0]);
+        assert_eq!(coarse.resize(4, 4, ResizeFilter::Area), nearest);
+
+        // Bicubic interpolation reproduces linear ramps exactly (away from the clamped edges)
+        let ramp = Table::new(Array::from_vec((0..8).map(|x| x as f64).collect()), 8, 1);
+        let bicubic = ramp.resize(16, 1, ResizeFilter::Bicubic);
+        for x in 4..12 {
+            assert!((bicubic.get(x, 0) - (x as f64 - 0.5) / 2.0).abs() < 1e-9);
+        }
+        let step = Table::new(Array::from_vec(vec![0u8, 0, 255, 255]), 4, 1);
+        let overshoot = step.resize(8, 1, ResizeFilter::Bicubic);
+        assert_eq!(overshoot.get(0, 0), 0);
+        assert_eq!(overshoot.get(7, 0), 255);
+    }
+
+    #[test]
+    fn test_morphology(){
+        use crate::kernels;
+
+        let grid = Table::new(Array::from_vec(vec![
+            0u8, 0, 0, 0, 0, 0,
+            0, 5, 5, 5, 0, 0,
+            0, 5, 5, 5, 0, 9,
+            0, 5, 0, 5, 0, 0,
+            0, 0, 0, 0, 0, 0
+        ]), 6, 5);
+        let square = kernels::square_element(3);
+        let eroded = grid.erode(&square);
+        assert_eq!(eroded.get_row(2), vec![0; 6]);
+        let dilated = grid.dilate(&square);
+        assert_eq!(dilated.get_row(0), vec![5, 5, 5, 5, 5, 0]);
+        assert_eq!(dilated.get_row(3), vec![5, 5, 5, 5, 9, 9]);
+        assert_eq!(dilated.get_row(4), vec![5, 5, 5, 5, 5, 0]);
+        let closed = grid.close(&square);
+        assert_eq!(closed.get(2, 3), 5);
+        assert_eq!(closed.get(5, 2), 9);
+        let opened = grid.open(&kernels::cross_element());
+        assert_eq!(opened.get(5, 2), 0);
+        assert_eq!(opened, grid.erode(&kernels::cross_element()).dilate(&kernels::cross_element()));
+        assert_eq!(grid.open(&kernels::square_element(1)), grid);
+        assert!(grid.try_erode(&Table::new(Array::create_filled(4, false), 2, 2)).is_err());
+
+        // An asymmetric structuring element: dilation must be the reflection of erosion
+        let right = Table::new(Array::from_vec(vec![false, false, true]), 3, 1);
+        let line = Table::new(Array::from_vec(vec![0i32, 0, 7, 0, 0]), 5, 1);
+        assert_eq!(line.erode(&right).get_row(0), vec![0, 7, 0, 0, 0]);
+        assert_eq!(line.dilate(&right).get_row(0), vec![0, 0, 0, 7, 0]);
+        assert_eq!(kernels::disk_element(2).get_row(0), vec![false, false, true, false, false]);
+        assert_eq!(kernels::disk_element(2).get_row(1), vec![false, true, true, true, false]);
+
+        let occupancy = Table::new(Array::create_filled(9 * 7, false), 9, 7);
+        occupancy.set(1, 1, true);
+        occupancy.set(7, 4, true);
+        occupancy.set(4, 6, true);
+        let exact = occupancy.euclidean_distance_transform();
+        let chamfer = occupancy.chamfer_distance_transform();
+        for y in 0..7 {
+            for x in 0..9 {
+                let expected = [(1, 1), (7, 4), (4, 6)].iter().map(|(px, py): &(i32, i32)| {
+                    (((x as i32 - px).pow(2) + (y as i32 - py).pow(2)) as f64).sqrt()
+                }).fold(f64::INFINITY, f64::min);
+                assert!((exact.get(x, y) - expected).abs() < 1e-9, "({}, {})", x, y);
+                assert!(chamfer.get(x, y) >= expected - 1e-9 && chamfer.get(x, y) <= expected * 1.09 + 1e-9);
+            }
+        }
+        assert_eq!(exact.get(7, 4), 0.0);
+        assert_eq!(chamfer.get(3, 3), 2.0 * 2.0f64.sqrt());
+
+        let empty = Table::new(Array::create_filled(4, false), 2, 2);
+        assert!(empty.euclidean_distance_transform().get(1, 1).is_infinite());
+        assert!(empty.chamfer_distance_transform().get(0, 1).is_infinite());
+    }
+
+    #[test]
+    fn test_connected_components(){
+        use crate::Connectivity;
+
+        let heatmap = Table::new(Array::from_vec(vec![
+            1u32, 1, 0, 0, 2, 0,
+            0, 1, 0, 3, 3, 0,
+            0, 0, 4, 0, 0, 0,
+            5, 0, 0, 0, 6, 6,
+            5, 5, 0, 6, 6, 0
+        ]), 6, 5);
+        let (labels, components) = heatmap.label_components(Connectivity::Four);
+        assert_eq!(components.len(), 5);
+        assert_eq!(labels.get_row(0), vec![1, 1, 0, 0, 2, 0]);
+        assert_eq!(labels.get_row(1), vec![0, 1, 0, 2, 2, 0]);
+        assert_eq!(labels.get_row(4), vec![4, 4, 0, 5, 5, 0]);
+        assert_eq!(components[1].area, 3);
+        assert_eq!(components[1].sum, 8.0);
+        assert_eq!((components[1].min_x, components[1].min_y, components[1].max_x, components[1].max_y), (3, 0, 4, 1));
+        assert_eq!((components[1].centroid_x, components[1].centroid_y), (11.0 / 3.0, 2.0 / 3.0));
+        assert_eq!(components[2].label, 3);
+        assert_eq!(components[2].area, 1);
+
+        // The element at (2, 2) connects 2 provisional labels diagonally, which requires merging them
+        let (labels, components) = heatmap.label_components(Connectivity::Eight);
+        assert_eq!(components.len(), 3);
+        assert_eq!(labels.get_row(2), vec![0, 0, 1, 0, 0, 0]);
+        assert_eq!(labels.get(4, 0), 1);
+        assert_eq!(components[0].area, 7);
+        assert_eq!(components[0].sum, 15.0);
+        assert_eq!((components[0].max_x, components[0].max_y), (4, 2));
+        assert_eq!(components[2].area, 4);
+
+        let shape = Table::new(Array::from_vec(vec![
+            1.0, 0.0, 1.0,
+            1.0, 0.0, 1.0,
+            1.0, 1.0, 1.0
+        ]), 3, 3);
+        let (labels, components) = shape.label_components_by(Connectivity::Four, |value| value > 0.5);
+        assert_eq!(components.len(), 1);
+        assert_eq!(labels.get_row(0), vec![1, 0, 1]);
+        assert_eq!((components[0].min_x, components[0].max_x), (0, 2));
+        let (labels, components) = shape.label_components_by(Connectivity::Four, |value| value < 0.5);
+        assert_eq!(components.len(), 1);
+        assert_eq!(labels.get_column(1), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn test_histograms(){
+        let array = Array::from_vec(vec![0u8, 10, 20, 30, 40, 50, 60, 70, 200, 255]);
+        let histogram = array.histogram(4, (0.0, 80.0));
+        assert_eq!(4, histogram.get_bin_count());
+        assert_eq!((0.0, 80.0), histogram.get_range());
+        assert_eq!(2, histogram.get_count(0));
+        assert_eq!(2, histogram.get_count(3));
+        assert_eq!(8, histogram.get_total());
+        assert_eq!(2, histogram.get_outside_count());
+        assert_eq!(Some(1), histogram.bin_for(20.0));
+        assert_eq!(Some(3), histogram.bin_for(80.0));
+        assert_eq!(None, histogram.bin_for(80.5));
+        assert_eq!((20.0, 40.0), histogram.get_bin_range(1));
+
+        let cumulative = histogram.cumulative();
+        assert_eq!(vec![2, 4, 6, 8], (0..4).map(|bin| cumulative.get(bin)).collect::<Vec<u64>>());
+        assert_eq!(Some(40.0), histogram.percentile(50.0));
+        assert_eq!(Some(10.0), histogram.percentile(12.5));
+        assert_eq!(Some(80.0), histogram.percentile(100.0));
+        assert_eq!(None, histogram.percentile(101.0));
+        assert_eq!(None, Array::create_filled(3, 100u8).histogram(2, (0.0, 10.0)).percentile(50.0));
+
+        // The u8 fast path must give the same result as the generic path
+        let floats = Array::from_vec((0..10).map(|index| array.get(index) as f64).collect());
+        let float_histogram = floats.histogram(4, (0.0, 80.0));
+        for bin in 0..4 {
+            assert_eq!(histogram.get_count(bin), float_histogram.get_count(bin));
+        }
+        assert_eq!(histogram.get_outside_count(), float_histogram.get_outside_count());
+
+        let wide = Array::from_vec(vec![1000u16, 60000, 65535, 3]);
+        let wide_histogram = wide.histogram(2, (0.0, 65535.0));
+        assert_eq!(2, wide_histogram.get_count(0));
+        assert_eq!(2, wide_histogram.get_count(1));
+
+        assert_eq!(Err(MemoryStructuresError::ZeroSize), array.try_histogram(0, (0.0, 1.0)).map(|_| ()));
+        assert_eq!(Err(MemoryStructuresError::InvalidRange), array.try_histogram(2, (1.0, 1.0)).map(|_| ()));
+        assert_eq!(Err(MemoryStructuresError::InvalidRange), array.try_histogram(2, (0.0, f64::NAN)).map(|_| ()));
+
+        let table = Table::new(Array::from_vec(vec![
+            1u8, 1, 1, 1,
+            1, 1, 2, 2,
+            3, 3, 3, 200
+        ]), 4, 3);
+        let table_histogram = table.histogram(256, (0.0, 255.0));
+        assert_eq!(6, table_histogram.get_count(1));
+        assert_eq!(1, table_histogram.get_count(200));
+
+        // Equalization spreads the values over the range, while preserving their order
+        table.equalize(256, (0.0, 255.0));
+        assert_eq!(0, table.get(0, 0));
+        assert_eq!(0, table.get(1, 1));
+        assert_eq!(85, table.get(2, 1));
+        assert_eq!(213, table.get(0, 2));
+        assert_eq!(255, table.get(3, 2));
+
+        let outside = Array::from_vec(vec![-5.0, 1.0, 3.0, 5.0, 7.0]);
+        outside.equalize(3, (0.0, 6.0));
+        assert_eq!(vec![-5.0, 0.0, 3.0, 6.0, 7.0], (0..5).map(|index| outside.get(index)).collect::<Vec<f64>>());
+        assert_eq!(Err(MemoryStructuresError::InvalidRange), outside.try_equalize(3, (6.0, 0.0)));
+    }
+
+    #[test]
+    fn test_bit_structures(){
+        use crate::{BitArray, BitTable};
+
+        let bits = BitArray::create_filled(150, false);
+        assert_eq!(150, bits.len());
+        assert_eq!(3, bits.get_words().len());
+        assert_eq!(0, bits.count_ones());
+        bits.set(3, true);
+        bits.set(149, true);
+        assert!(bits.get(3));
+        assert!(!bits.get(4));
+        assert!(bits.get(149));
+        assert_eq!(Err(MemoryStructuresError::IndexOutOfBounds { index: 150, size: 150 }), bits.try_get(150));
+
+        // Ranges that cross word boundaries
+        bits.set_some(60, 80, true);
+        assert_eq!(82, bits.count_ones());
+        assert!(!bits.get(59));
+        assert!(bits.get(60) && bits.get(127) && bits.get(139));
+        assert!(!bits.get(140));
+        bits.set_some(64, 64, false);
+        assert_eq!(18, bits.count_ones());
+        assert_eq!(5, bits.count_ones_some(0, 64));
+        assert_eq!(0, bits.count_ones_some(64, 64));
+        assert_eq!(Err(MemoryStructuresError::RangeOutOfBounds { start_index: 100, amount: 51, size: 150 }), bits.try_set_some(100, 51, true));
+
+        // Inverting must not set the unused bits of the last word
+        let inverted = !&bits;
+        assert_eq!(132, inverted.count_ones());
+        assert_eq!(150, (&bits | &inverted).count_ones());
+        assert_eq!(0, (&bits & &inverted).count_ones());
+        assert_eq!(150, (&bits ^ &inverted).count_ones());
+        let full = BitArray::create_filled(150, true);
+        assert_eq!(150, full.count_ones());
+        full.xor(&bits);
+        assert_eq!(inverted, full);
+        full.and(&full);
+        assert_eq!(inverted, full);
+        full.xor(&full);
+        assert_eq!(0, full.count_ones());
+        assert_eq!(Err(MemoryStructuresError::LengthMismatch { expected_length: 150, length: 10 }), full.try_and(&BitArray::create_filled(10, true)));
+        assert_eq!(Err(MemoryStructuresError::ZeroSize), BitArray::try_create_filled(0, true));
+
+        let table = BitTable::create_filled(100, 50, false);
+        table.set(99, 49, true);
+        assert!(table.get(99, 49));
+        assert!(table.get_bits().get(99 + 49 * 100));
+        table.set_rect(10, 5, 19, 9, true);
+        assert_eq!(51, table.count_ones());
+        assert_eq!(4, table.count_ones_rect(18, 8, 30, 20));
+        assert_eq!(Err(MemoryStructuresError::CoordinatesOutOfBounds { x: 100, y: 0, width: 100, height: 50 }), table.try_get(100, 0));
+        assert_eq!(Err(MemoryStructuresError::InvalidRect { min_x: 5, min_y: 0, max_x: 4, max_y: 0, width: 100, height: 50 }), table.try_set_rect(5, 0, 4, 0, true));
+
+        // Full rows are set in a single range
+        table.set_rect(0, 20, 99, 21, true);
+        assert_eq!(251, table.count_ones());
+
+        // Drawing with true sets the elements and drawing with false clears them
+        let mask = BitTable::create_filled(10, 10, false);
+        mask.draw_line(0, 0, 9, 9, true);
+        mask.draw_line(0, 5, 9, 5, true);
+        assert_eq!(19, mask.count_ones());
+        assert!(mask.get(4, 4) && mask.get(0, 5) && mask.get(9, 5));
+        mask.draw_line(0, 5, 9, 5, false);
+        assert_eq!(9, mask.count_ones());
+        assert!(!mask.get(5, 5));
+
+        let other = BitTable::create_filled(10, 10, true);
+        other.set_rect(0, 0, 9, 4, false);
+        assert_eq!(4, (&mask & &other).count_ones());
+        mask.invert();
+        assert_eq!(91, mask.count_ones());
+        assert_eq!(Err(MemoryStructuresError::DimensionMismatch { expected_width: 10, expected_height: 10, width: 100, height: 50 }), mask.try_or(&table));
+    }
+
+    #[test]
+    fn test_sparse_tables(){
+        use crate::SparseTable;
+
+        let table = SparseTable::new(100_000, 100_000, 64, 32, 7u32);
+        assert_eq!(7, table.get(99_999, 99_999));
+        assert_eq!(0, table.get_chunk_count());
+        assert_eq!(0, table.get_memory_usage());
+        assert_eq!(40_000_000_000, table.get_dense_memory_usage());
+
+        table.set(70, 40, 3);
+        assert_eq!(3, table.get(70, 40));
+        assert_eq!(7, table.get(71, 40));
+        assert!(table.is_chunk_allocated(1, 1));
+        assert_eq!(1, table.get_chunk_count());
+        assert_eq!(64 * 32 * 4, table.get_memory_usage());
+        assert_eq!(Err(MemoryStructuresError::CoordinatesOutOfBounds { x: 100_000, y: 0, width: 100_000, height: 100_000 }), table.try_get(100_000, 0));
+        assert!(table.try_set(0, 100_000, 1).is_err());
+
+        // A rectangle that crosses chunk boundaries allocates every chunk it touches
+        table.add_unchecked_rect(60, 30, 130, 33, 10);
+        assert_eq!(6, table.get_chunk_count());
+        assert_eq!(17, table.get(60, 30));
+        assert_eq!(17, table.get(130, 33));
+        assert_eq!(7, table.get(131, 33));
+        assert_eq!(7, table.get(60, 34));
+        assert_eq!(3, table.get(70, 40));
+
+        let mut chunks = Vec::new();
+        let mut changed = 0;
+        table.for_each_chunk(|chunk_x, chunk_y, array| {
+            chunks.push((chunk_x, chunk_y));
+            changed += (0..array.len()).filter(|index| array.get(*index) != 7).count();
+        });
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], chunks);
+        assert_eq!(71 * 4 + 1, changed);
+
+        // Existing chunks can be modified through the table while iterating, but new chunks can't be allocated
+        table.for_each_chunk(|chunk_x, chunk_y, _| table.set(chunk_x * 64, chunk_y * 32, 5));
+        assert_eq!(5, table.get(64, 32));
+        assert_eq!(6, table.get_chunk_count());
+        catch_unwind(std::panic::AssertUnwindSafe(|| table.for_each_chunk(|_, _, _| table.set(5000, 5000, 1)))).unwrap_err();
+        assert!(!table.is_chunk_allocated(5000 / 64, 5000 / 32));
+
+        // Drawing works like it does on a Table
+        let lines = SparseTable::new(1000, 1000, 16, 16, 0i32);
+        let dense = Table::new(Array::create_filled(1000 * 1000, 0i32), 1000, 1000);
+        lines.draw_line(0, 0, 999, 999, 1);
+        dense.draw_line(0, 0, 999, 999, 1);
+        assert_eq!(63, lines.get_chunk_count());
+        for index in 0..999 {
+            assert_eq!(dense.get(index, index), lines.get(index, index));
+            assert_eq!(0, lines.get(index, index + 1));
+        }
+        assert_ne!(0, lines.get(500, 500));
+        lines.draw_line(10, 900, 10, 5000, 2);
+        assert_eq!(2, lines.get(10, 999));
+
+        assert!(lines.remove_chunk(0, 0));
+        assert!(!lines.remove_chunk(0, 0));
+        assert_eq!(0, lines.get(3, 3));
+        lines.add_unchecked(500, 20, 5);
+        lines.add_unchecked(500, 20, -5);
+        assert_eq!(1, lines.compact());
+        assert!(!lines.is_chunk_allocated(31, 1));
+        assert_eq!(dense.get(500, 500), lines.get(500, 500));
+        lines.clear();
+        assert_eq!(0, lines.get_chunk_count());
+
+        assert_eq!(Err(MemoryStructuresError::ZeroDimension { width: 0, height: 16 }), SparseTable::try_new(10, 10, 0, 16, 0).map(|_| ()));
+    }
+
+    #[test]
+    fn test_table_strides(){
+        // A column-major table
+        let transposed = Table::with_strides(Array::from_vec((0..6).collect::<Vec<u32>>()), 0, 2, 3, 3, 1);
+        assert_eq!(transposed.get_row(0), vec![0, 3]);
+        assert_eq!(transposed.get_row(2), vec![2, 5]);
+        transposed.add_row(1, 10);
+        transposed.draw_line(0, 0, 0, 2, 100);
+        assert_eq!(transposed.get_column(0), vec![100, 111, 102]);
+        assert_eq!(transposed.get_column(1), vec![3, 14, 5]);
+
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 2, 2, 2, 1).is_ok());
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 2, 2, 1, 1).is_err());
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 1, 3, 0, 0).is_err());
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 2, 2, 2, 2).is_err());
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 2, 2, 1, 2).is_ok());
+        assert!(Table::try_with_strides(Array::create_filled(10, 0), 0, 2, 2, 1, 9).is_err());
+    }
+
+    #[test]
+    fn test_fallible_api(){
+        assert_eq!(Array::<u8>::try_create_garbage(0).err(), Some(MemoryStructuresError::ZeroSize));
+
+        let array = Array::try_create_filled(10, 3u8).unwrap();
+        assert_eq!(array.try_get(9), Ok(3));
+        assert_eq!(array.try_get(10), Err(MemoryStructuresError::IndexOutOfBounds { index: 10, size: 10 }));
+        assert_eq!(array.try_set(10, 1), Err(MemoryStructuresError::IndexOutOfBounds { index: 10, size: 10 }));
+        array.try_set(2, 5).unwrap();
+        assert_eq!(array.get(2), 5);
+        array.try_add(2, 1).unwrap();
+        assert_eq!(array.get(2), 6);
+
+        assert_eq!(array.try_set_some(8, 3, 0), Err(MemoryStructuresError::RangeOutOfBounds { start_index: 8, amount: 3, size: 10 }));
+        assert_eq!(array.try_add_some(usize::MAX, 2, 1), Err(MemoryStructuresError::Overflow));
+        assert_eq!(array.get(8), 3);
+        array.try_saturating_add_some(8, 2, 255).unwrap();
+        assert_eq!(array.get(9), 255);
+        assert_eq!(array.try_create_vector(7, 3), Ok(vec![3, 255, 255]));
+
+        unsafe {
+            assert!(array.try_sharing_sub_array(5, 0).is_err());
+            assert!(array.try_sharing_sub_array(5, 6).is_err());
+            let sub_array = array.try_sharing_sub_array(5, 5).unwrap();
+            sub_array.set(0, 42);
+            assert_eq!(array.get(5), 42);
+        }
+
+        assert_eq!(Table::try_new(Array::create_filled(5, 0u8), 2, 3).err(), Some(MemoryStructuresError::ArrayTooSmall { required_size: 6, size: 5 }));
+        assert_eq!(Table::try_new(Array::create_filled(5, 0u8), 0, 3).err(), Some(MemoryStructuresError::ZeroDimension { width: 0, height: 3 }));
+
+        let table = Table::try_new(Array::create_filled(6, 0u8), 3, 2).unwrap();
+        assert_eq!(table.try_set(3, 0, 1), Err(MemoryStructuresError::CoordinatesOutOfBounds { x: 3, y: 0, width: 3, height: 2 }));
+        table.try_set(2, 1, 7).unwrap();
+        assert_eq!(table.try_get(2, 1), Ok(7));
+        assert!(table.try_set_row(2, 1).is_err());
+        assert!(table.try_set_column(3, 1).is_err());
+
+        let error: Box<dyn std::error::Error> = Box::new(MemoryStructuresError::IndexOutOfBounds { index: 10, size: 10 });
+        assert_eq!(error.to_string(), "Index is 10 and size is 10");
+    }
+
     #[test]
     fn test_table_graphics(){
 
@@ -177,6 +1689,6 @@ mod tests {
         table.draw_line(0, 0, 2, 9, 4);
 
         // Remove this line later since nobody will bother reading it anyway
-        table.print();
+        println!("{}", table);
     }
 }
\ No newline at end of file
@@ -0,0 +1,136 @@
+//! Arrays that are backed by a memory-mapped file. This module is only available on unix systems.
+
+use crate::Array;
+use crate::array::MemoryOwner;
+
+use std::fs::File;
+use std::io;
+use std::mem::size_of;
+use std::os::unix::io::AsRawFd;
+use std::ptr::null_mut;
+
+/// The way in which a file is mapped into memory by Array::map_file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMode {
+
+    /// The file only needs to be opened for reading, and it is mapped privately with copy-on-write
+    /// semantics. The Array can still be modified (all Array methods can modify elements through a
+    /// shared reference, so the mapping must be writable), but the first modification of a page
+    /// copies that page. The modifications are only visible to the Array (and its sharing copies)
+    /// and are never written back to the file.
+    CopyOnWrite,
+
+    /// The file must be opened for reading and writing. Modifications to the Array will be
+    /// written back to the file and are visible to other processes that map the same file.
+    ReadWrite
+}
+
+/// A memory mapping that will be unmapped when it is dropped.
+pub(crate) struct MemoryMapping {
+
+    address: *mut libc::c_void,
+    length: usize
+}
+
+impl MemoryMapping {
+
+    fn flush(&self) -> io::Result<()> {
+        if unsafe { libc::msync(self.address, self.length, libc::MS_SYNC) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MemoryMapping {
+
+    fn drop(&mut self){
+        unsafe {
+            libc::munmap(self.address, self.length);
+        }
+    }
+}
+
+/// Zero-sized types can't be mapped, since the length of the Array can't be derived from the file length
+fn check_element_size<T>() -> io::Result<()> {
+    if size_of::<T>() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Zero-sized types can't be mapped to a file"));
+    }
+    Ok(())
+}
+
+impl<T: Copy> Array<T> {
+
+    /// Creates an Array that is backed by the contents of the given file. The length of the Array
+    /// will be the length of the file divided by the size of T (rounded down). Mapping a file is
+    /// almost instant, even for huge files, because the operating system will only load the parts
+    /// of the file that are actually used.
+    ///
+    /// This will return an error if T is a zero-sized type, if the file is too small to hold a single
+    /// element or if the operating system refuses to map the file (for instance because the file was
+    /// not opened with the access needed for the given mode).
+    ///
+    /// # Safety
+    /// The caller must ensure that every bit pattern in the file is a valid value of T. Furthermore,
+    /// the file must not be truncated while the Array is in use, and modifications of the file by
+    /// other processes will be visible to this Array without any synchronization.
+    pub unsafe fn map_file(file: &File, mode: MapMode) -> io::Result<Array<T>> {
+        check_element_size::<T>()?;
+        let file_length = file.metadata()?.len() as usize;
+        let size = file_length / size_of::<T>();
+        if size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                "The file length is {}, which is too small to hold an element of {} bytes", file_length, size_of::<T>()
+            )));
+        }
+        let length = size * size_of::<T>();
+        let (protection, flags) = match mode {
+            MapMode::CopyOnWrite => (libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE),
+            MapMode::ReadWrite => (libc::PROT_READ | libc::PROT_WRITE, libc::MAP_SHARED)
+        };
+        let address = libc::mmap(null_mut(), length, protection, flags, file.as_raw_fd(), 0);
+        if address == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let mapping = MemoryMapping { address, length };
+        Ok(Array::from_owner(address as *mut T, size, MemoryOwner::Mapping(mapping)))
+    }
+
+    /// Resizes the given file such that it can hold exactly size elements of type T, and creates an
+    /// Array that is backed by the file in ReadWrite mode. Any bytes that are added to the file will
+    /// be 0. This is convenient to create a new file that will be persisted or shared with other processes.
+    ///
+    /// # Safety
+    /// The same requirements as for map_file apply. In particular, a sequence of zero bytes must be
+    /// a valid value of T if the file is extended.
+    pub unsafe fn map_file_with_size(file: &File, size: usize) -> io::Result<Array<T>> {
+        check_element_size::<T>()?;
+        if size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Attempted to map an array of length 0"));
+        }
+        let length = size.checked_mul(size_of::<T>()).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "The size of the file would overflow")
+        })?;
+        file.set_len(length as u64)?;
+        Array::map_file(file, MapMode::ReadWrite)
+    }
+}
+
+impl<T> Array<T> {
+
+    /// Checks if this Array owns a memory-mapped file. Sharing copies of such an Array will
+    /// return false.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.get_memory_owner(), Some(MemoryOwner::Mapping(_)))
+    }
+
+    /// Writes all modifications of this Array back to the file it was mapped from, and waits until
+    /// that is finished. This only has effect when this Array owns a file that was mapped in
+    /// ReadWrite mode: it does nothing for other Arrays.
+    pub fn flush(&self) -> io::Result<()> {
+        if let Some(MemoryOwner::Mapping(mapping)) = self.get_memory_owner() {
+            mapping.flush()?;
+        }
+        Ok(())
+    }
+}
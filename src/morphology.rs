@@ -0,0 +1,228 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+
+use std::f64::consts::SQRT_2;
+
+/// The morphological operations use a structuring element: a Table<bool> whose true elements form the
+/// neighborhood of each element. The center of the structuring element is at (width / 2, height / 2),
+/// just like the center of convolution kernels. Neighbors outside the table are ignored, so the edges
+/// of the table are neither eroded nor dilated by the missing elements.
+impl<T: Ord + Copy> Table<T> {
+
+    /// Computes the relative positions of the true elements of the structuring element. This returns an
+    /// error if the structuring element doesn't have any true elements.
+    fn try_neighborhood(element: &Table<bool>) -> Result<Vec<(isize, isize)>, MemoryStructuresError> {
+        let center_x = (element.get_width() / 2) as isize;
+        let center_y = (element.get_height() / 2) as isize;
+        let mut neighborhood = Vec::new();
+        for y in 0..element.get_height() {
+            for x in 0..element.get_width() {
+                if element.get_unchecked(x, y) {
+                    neighborhood.push((x as isize - center_x, y as isize - center_y));
+                }
+            }
+        }
+        if neighborhood.is_empty() {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        Ok(neighborhood)
+    }
+
+    /// Creates a new table whose element at (x, y) is the best element of this table at (x + dx, y + dy)
+    /// for all (dx, dy) in the neighborhood, where select chooses the better of 2 elements. If none of
+    /// these positions is inside this table, the element at (x, y) itself is used.
+    fn select_in_neighborhood<F: Fn(T, T) -> T>(&self, neighborhood: &[(isize, isize)], select: F) -> Table<T> {
+        let (width, height) = (self.get_width() as isize, self.get_height() as isize);
+        let mut elements = Vec::with_capacity(self.get_width() * self.get_height());
+        for y in 0..height {
+            for x in 0..width {
+                let neighbors = neighborhood.iter()
+                    .map(|(dx, dy)| (x + dx, y + dy))
+                    .filter(|(nx, ny)| *nx >= 0 && *nx < width && *ny >= 0 && *ny < height)
+                    .map(|(nx, ny)| self.get_unchecked(nx as usize, ny as usize));
+                elements.push(neighbors.reduce(&select).unwrap_or_else(|| self.get_unchecked(x as usize, y as usize)));
+            }
+        }
+        Table::new(Array::from_vec(elements), self.get_width(), self.get_height())
+    }
+
+    /// Creates a new table whose element at (x, y) is the smallest element of this table in the neighborhood
+    /// of (x, y) that is described by the structuring element. This will panic if the structuring element
+    /// doesn't have any true elements.
+    pub fn erode(&self, element: &Table<bool>) -> Table<T> {
+        match self.try_erode(element) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Erodes this table with the structuring element, just like erode. Instead of panicking, this will
+    /// return an error if the structuring element doesn't have any true elements.
+    pub fn try_erode(&self, element: &Table<bool>) -> Result<Table<T>, MemoryStructuresError> {
+        let neighborhood = Table::<T>::try_neighborhood(element)?;
+        Ok(self.select_in_neighborhood(&neighborhood, std::cmp::min))
+    }
+
+    /// Creates a new table whose element at (x, y) is the largest element of this table in the neighborhood
+    /// of (x, y) that is described by the reflected structuring element. The reflection makes dilation the
+    /// dual of erosion, which matters for structuring elements that are not symmetric. This will panic if
+    /// the structuring element doesn't have any true elements.
+    pub fn dilate(&self, element: &Table<bool>) -> Table<T> {
+        match self.try_dilate(element) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Dilates this table with the structuring element, just like dilate. Instead of panicking, this will
+    /// return an error if the structuring element doesn't have any true elements.
+    pub fn try_dilate(&self, element: &Table<bool>) -> Result<Table<T>, MemoryStructuresError> {
+        let reflected: Vec<(isize, isize)> = Table::<T>::try_neighborhood(element)?.into_iter().map(|(dx, dy)| (-dx, -dy)).collect();
+        Ok(self.select_in_neighborhood(&reflected, std::cmp::max))
+    }
+
+    /// Erodes and then dilates this table with the structuring element, which removes small bright
+    /// features. This will panic if the structuring element doesn't have any true elements.
+    pub fn open(&self, element: &Table<bool>) -> Table<T> {
+        match self.try_open(element) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Opens this table with the structuring element, just like open. Instead of panicking, this will
+    /// return an error if the structuring element doesn't have any true elements.
+    pub fn try_open(&self, element: &Table<bool>) -> Result<Table<T>, MemoryStructuresError> {
+        self.try_erode(element)?.try_dilate(element)
+    }
+
+    /// Dilates and then erodes this table with the structuring element, which fills small dark gaps.
+    /// This will panic if the structuring element doesn't have any true elements.
+    pub fn close(&self, element: &Table<bool>) -> Table<T> {
+        match self.try_close(element) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Closes this table with the structuring element, just like close. Instead of panicking, this will
+    /// return an error if the structuring element doesn't have any true elements.
+    pub fn try_close(&self, element: &Table<bool>) -> Result<Table<T>, MemoryStructuresError> {
+        self.try_dilate(element)?.try_erode(element)
+    }
+}
+
+/// Computes the squared distance transform of a 1-dimensional function in place, using the lower envelope
+/// of parabolas (Felzenszwalb and Huttenlocher, "Distance Transforms of Sampled Functions"). Afterwards,
+/// values[q] is the minimum of (q - p)^2 + values[p] over all p. The locations and boundaries are buffers
+/// that must have the same length as values and values + 1.
+fn squared_distance_1d(values: &mut [f64], locations: &mut [usize], boundaries: &mut [f64]){
+    let squared = |index: usize| (index * index) as f64;
+    let intersection = |values: &[f64], p: usize, q: usize| {
+        ((values[q] + squared(q)) - (values[p] + squared(p))) / (2.0 * q as f64 - 2.0 * p as f64)
+    };
+
+    // Only the finite values contribute a parabola
+    let mut parabolas = 0;
+    for q in 0..values.len() {
+        if values[q].is_infinite() {
+            continue;
+        }
+        while parabolas > 0 && intersection(values, locations[parabolas - 1], q) <= boundaries[parabolas - 1] {
+            parabolas -= 1;
+        }
+        locations[parabolas] = q;
+        boundaries[parabolas] = if parabolas == 0 { f64::NEG_INFINITY } else { intersection(values, locations[parabolas - 1], q) };
+        boundaries[parabolas + 1] = f64::INFINITY;
+        parabolas += 1;
+    }
+    if parabolas == 0 {
+        return;
+    }
+
+    let envelope: Vec<(usize, f64)> = (0..parabolas).map(|index| (locations[index], values[locations[index]])).collect();
+    let mut current = 0;
+    for (q, value) in values.iter_mut().enumerate() {
+        while boundaries[current + 1] < q as f64 {
+            current += 1;
+        }
+        let (location, location_value) = envelope[current];
+        let distance = q as f64 - location as f64;
+        *value = distance * distance + location_value;
+    }
+}
+
+impl Table<bool> {
+
+    /// Computes the exact Euclidean distance from every element to the nearest true element, using the
+    /// linear-time algorithm of Felzenszwalb and Huttenlocher. True elements get distance 0. If this table
+    /// doesn't contain any true element, all distances are infinite.
+    pub fn euclidean_distance_transform(&self) -> Table<f64> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut distances: Vec<f64> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            distances.extend(self.row_iter(y).map(|element| if *element { 0.0 } else { f64::INFINITY }));
+        }
+
+        let longest = std::cmp::max(width, height);
+        let mut values = vec![0.0; longest];
+        let mut locations = vec![0; longest];
+        let mut boundaries = vec![0.0; longest + 1];
+
+        // Transform the columns first and then the rows, which gives the squared distances
+        for x in 0..width {
+            for y in 0..height {
+                values[y] = distances[x + y * width];
+            }
+            squared_distance_1d(&mut values[..height], &mut locations, &mut boundaries);
+            for y in 0..height {
+                distances[x + y * width] = values[y];
+            }
+        }
+        for row in distances.chunks_mut(width) {
+            squared_distance_1d(row, &mut locations, &mut boundaries);
+            row.iter_mut().for_each(|distance| *distance = distance.sqrt());
+        }
+        Table::new(Array::from_vec(distances), width, height)
+    }
+
+    /// Approximates the Euclidean distance from every element to the nearest true element with the chamfer
+    /// distance: the length of the shortest path through horizontal and vertical steps (length 1) and
+    /// diagonal steps (length sqrt(2)). This is computed with 2 passes over the table, which is faster than
+    /// euclidean_distance_transform, but it can overestimate the distance by up to about 8%. True elements
+    /// get distance 0. If this table doesn't contain any true element, all distances are infinite.
+    pub fn chamfer_distance_transform(&self) -> Table<f64> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut distances: Vec<f64> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            distances.extend(self.row_iter(y).map(|element| if *element { 0.0 } else { f64::INFINITY }));
+        }
+
+        // The forward pass uses the neighbors above and to the left, the backward pass the others
+        let forward = [(-1, 0, 1.0), (-1, -1, SQRT_2), (0, -1, 1.0), (1, -1, SQRT_2)];
+        let backward = [(1, 0, 1.0), (1, 1, SQRT_2), (0, 1, 1.0), (-1, 1, SQRT_2)];
+        let mut relax = |x: usize, y: usize, mask: &[(isize, isize, f64)]| {
+            for (dx, dy, step) in mask.iter() {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if nx >= 0 && (nx as usize) < width && ny >= 0 && (ny as usize) < height {
+                    let candidate = distances[nx as usize + ny as usize * width] + step;
+                    if candidate < distances[x + y * width] {
+                        distances[x + y * width] = candidate;
+                    }
+                }
+            }
+        };
+        for y in 0..height {
+            for x in 0..width {
+                relax(x, y, &forward);
+            }
+        }
+        for y in (0..height).rev() {
+            for x in (0..width).rev() {
+                relax(x, y, &backward);
+            }
+        }
+        Table::new(Array::from_vec(distances), width, height)
+    }
+}
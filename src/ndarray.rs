@@ -0,0 +1,381 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+
+use std::ops::{AddAssign, Range};
+
+/// An N-dimensional view on an Array. The element at index [i0, i1, ...] is stored at array index
+/// offset + i0 * strides[0] + i1 * strides[1] + ... NdArrays created with NdArray::new use the
+/// row-major layout (the last axis is contiguous), but slicing, permuting axes and broadcasting
+/// produce NdArrays with other offsets and strides. A stride of 0 is allowed: all elements along
+/// such an axis share the same storage, which is how broadcasting works.
+pub struct NdArray<T, const N: usize> {
+
+    array: Array<T>,
+
+    shape: [usize; N],
+    strides: [usize; N],
+    offset: usize
+}
+
+impl<T, const N: usize> NdArray<T, N> {
+
+    /// Creates a new NdArray with the given shape that is backed by the given Array, using the
+    /// row-major layout. This will panic if the shape contains a 0 or if the array is too small.
+    pub fn new(array: Array<T>, shape: [usize; N]) -> NdArray<T, N> {
+        match NdArray::try_new(array, shape) {
+            Ok(nd_array) => nd_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new NdArray with the given shape, just like new.
+    /// Instead of panicking, this will return an error.
+    pub fn try_new(array: Array<T>, shape: [usize; N]) -> Result<NdArray<T, N>, MemoryStructuresError> {
+        let mut strides = [0; N];
+        let mut stride: usize = 1;
+        for axis in (0..N).rev() {
+            strides[axis] = stride;
+            stride = stride.checked_mul(shape[axis]).ok_or(MemoryStructuresError::Overflow)?;
+        }
+        NdArray::try_with_strides(array, 0, shape, strides)
+    }
+
+    /// Creates a new NdArray with the given shape, strides and offset that is backed by the given Array.
+    /// This will panic if the shape contains a 0 or if the array is too small.
+    pub fn with_strides(array: Array<T>, offset: usize, shape: [usize; N], strides: [usize; N]) -> NdArray<T, N> {
+        match NdArray::try_with_strides(array, offset, shape, strides) {
+            Ok(nd_array) => nd_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new NdArray with the given shape, strides and offset, just like with_strides.
+    /// Instead of panicking, this will return an error.
+    pub fn try_with_strides(array: Array<T>, offset: usize, shape: [usize; N], strides: [usize; N]) -> Result<NdArray<T, N>, MemoryStructuresError> {
+        if shape.contains(&0) {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        let mut last_index = offset;
+        for axis in 0..N {
+            last_index = (shape[axis] - 1).checked_mul(strides[axis])
+                .and_then(|distance| distance.checked_add(last_index))
+                .ok_or(MemoryStructuresError::Overflow)?;
+        }
+
+        // This test ensures that any operation within the shape will also be within the Array bounds.
+        if array.try_check_bound(last_index).is_err() {
+            return Err(MemoryStructuresError::ArrayTooSmall { required_size: last_index + 1, size: array.len() });
+        }
+        Ok(NdArray {
+            array,
+            shape,
+            strides,
+            offset
+        })
+    }
+
+    pub fn get_shape(&self) -> [usize; N] {
+        self.shape
+    }
+
+    pub fn get_strides(&self) -> [usize; N] {
+        self.strides
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of elements in this NdArray, which is the product of its shape
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Checks if this NdArray has no elements. Since shapes can't contain 0, this will always return false.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Creates an NdArray that shares its data with this NdArray and has the same shape, strides and offset.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as Array::sharing_copy.
+    pub unsafe fn sharing_copy(&self) -> NdArray<T, N> {
+        NdArray {
+            array: self.array.sharing_copy(),
+            shape: self.shape,
+            strides: self.strides,
+            offset: self.offset
+        }
+    }
+
+    pub fn unchecked_index_for(&self, index: [usize; N]) -> usize {
+        self.offset + index.iter().zip(self.strides.iter()).map(|(position, stride)| position * stride).sum::<usize>()
+    }
+
+    /// Gets the array index for the given index. This will panic if the index is outside the shape.
+    pub fn index_for(&self, index: [usize; N]) -> usize {
+        match self.try_index_for(index) {
+            Ok(array_index) => array_index,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the array index for the given index. This will return an error if the index is outside the shape.
+    pub fn try_index_for(&self, index: [usize; N]) -> Result<usize, MemoryStructuresError> {
+        if (0..N).any(|axis| index[axis] >= self.shape[axis]) {
+            return Err(MemoryStructuresError::NdIndexOutOfBounds { index: index.to_vec(), shape: self.shape.to_vec() });
+        }
+        Ok(self.unchecked_index_for(index))
+    }
+
+    pub fn set(&self, index: [usize; N], value: T){
+        self.array.set_unchecked(self.index_for(index), value);
+    }
+
+    pub fn try_set(&self, index: [usize; N], value: T) -> Result<(), MemoryStructuresError> {
+        self.array.set_unchecked(self.try_index_for(index)?, value);
+        Ok(())
+    }
+
+    pub fn set_unchecked(&self, index: [usize; N], value: T){
+        self.array.set_unchecked(self.unchecked_index_for(index), value);
+    }
+
+    pub fn get_ref(&self, index: [usize; N]) -> &T {
+        self.array.get_unchecked_ref(self.index_for(index))
+    }
+
+    pub fn get_unchecked_ref(&self, index: [usize; N]) -> &T {
+        self.array.get_unchecked_ref(self.unchecked_index_for(index))
+    }
+
+    pub fn get_mut_ref(&self, index: [usize; N]) -> &mut T {
+        self.array.get_unchecked_mut_ref(self.index_for(index))
+    }
+
+    pub fn get_unchecked_mut_ref(&self, index: [usize; N]) -> &mut T {
+        self.array.get_unchecked_mut_ref(self.unchecked_index_for(index))
+    }
+
+    /// Creates an iterator over all indices within the shape of this NdArray, in row-major order
+    /// (the index along the last axis changes the fastest).
+    pub fn indices(&self) -> NdIndices<N> {
+        NdIndices {
+            shape: self.shape,
+            next_index: Some([0; N])
+        }
+    }
+
+    /// Creates an iterator over references to all elements of this NdArray, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.indices().map(move |index| self.get_unchecked_ref(index))
+    }
+
+    /// Restricts this NdArray to the given range along each axis. The ranges are relative to the current
+    /// shape and the returned NdArray shares its data with this NdArray. This will panic if a range is
+    /// empty or exceeds the shape.
+    pub fn into_slice(self, ranges: [Range<usize>; N]) -> NdArray<T, N> {
+        match self.try_into_slice(ranges) {
+            Ok(nd_array) => nd_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Restricts this NdArray to the given range along each axis, just like into_slice.
+    /// Instead of panicking, this will return an error.
+    pub fn try_into_slice(mut self, ranges: [Range<usize>; N]) -> Result<NdArray<T, N>, MemoryStructuresError> {
+        for (range, size) in ranges.iter().zip(self.shape.iter()) {
+            if range.start >= range.end {
+                return Err(MemoryStructuresError::ZeroSize);
+            }
+            if range.end > *size {
+                return Err(MemoryStructuresError::RangeOutOfBounds {
+                    start_index: range.start, amount: range.end - range.start, size: *size
+                });
+            }
+        }
+        self.offset = self.unchecked_index_for(ranges.clone().map(|range| range.start));
+        self.shape = ranges.map(|range| range.end - range.start);
+        Ok(self)
+    }
+
+    /// Reorders the axes of this NdArray: axis i of the result is axis permutation[i] of this NdArray.
+    /// Only the shape and strides are permuted, so no elements are moved. This will panic if the
+    /// permutation doesn't contain every axis exactly once.
+    pub fn into_permuted_axes(self, permutation: [usize; N]) -> NdArray<T, N> {
+        match self.try_into_permuted_axes(permutation) {
+            Ok(nd_array) => nd_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Reorders the axes of this NdArray, just like into_permuted_axes.
+    /// Instead of panicking, this will return an error.
+    pub fn try_into_permuted_axes(mut self, permutation: [usize; N]) -> Result<NdArray<T, N>, MemoryStructuresError> {
+        let mut used = [false; N];
+        for axis in permutation.iter() {
+            if *axis >= N || used[*axis] {
+                return Err(MemoryStructuresError::InvalidAxisPermutation { permutation: permutation.to_vec() });
+            }
+            used[*axis] = true;
+        }
+        let old_shape = self.shape;
+        let old_strides = self.strides;
+        self.shape = permutation.map(|axis| old_shape[axis]);
+        self.strides = permutation.map(|axis| old_strides[axis]);
+        Ok(self)
+    }
+
+    /// Reverses the order of the axes of this NdArray. For 2 dimensions, this is the matrix transpose.
+    pub fn into_transposed(self) -> NdArray<T, N> {
+        let mut permutation = [0; N];
+        for (axis, old_axis) in permutation.iter_mut().enumerate() {
+            *old_axis = N - 1 - axis;
+        }
+        self.into_permuted_axes(permutation)
+    }
+
+    /// Expands the axes of size 1 of this NdArray to the given shape by giving them a stride of 0.
+    /// Every other axis must already have the size in the target shape. This will panic if the shapes
+    /// are not compatible.
+    pub fn into_broadcast(self, shape: [usize; N]) -> NdArray<T, N> {
+        match self.try_into_broadcast(shape) {
+            Ok(nd_array) => nd_array,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Expands the axes of size 1 of this NdArray to the given shape, just like into_broadcast.
+    /// Instead of panicking, this will return an error.
+    pub fn try_into_broadcast(mut self, shape: [usize; N]) -> Result<NdArray<T, N>, MemoryStructuresError> {
+        self.strides = self.broadcast_strides(shape)?;
+        self.shape = shape;
+        Ok(self)
+    }
+
+    /// Computes the strides that this NdArray would have when it would be broadcast to the given shape
+    fn broadcast_strides(&self, shape: [usize; N]) -> Result<[usize; N], MemoryStructuresError> {
+        let mut strides = self.strides;
+        for axis in 0..N {
+            if shape[axis] == 0 || (self.shape[axis] != shape[axis] && self.shape[axis] != 1) {
+                return Err(MemoryStructuresError::IncompatibleShapes { shape: self.shape.to_vec(), target: shape.to_vec() });
+            }
+            if self.shape[axis] != shape[axis] {
+                strides[axis] = 0;
+            }
+        }
+        Ok(strides)
+    }
+}
+
+/// An iterator over all indices within a shape, in row-major order
+pub struct NdIndices<const N: usize> {
+
+    shape: [usize; N],
+    next_index: Option<[usize; N]>
+}
+
+impl<const N: usize> Iterator for NdIndices<N> {
+
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        let current = self.next_index?;
+        let mut next = current;
+        let mut axis = N;
+        loop {
+            if axis == 0 {
+                self.next_index = None;
+                break;
+            }
+            axis -= 1;
+            next[axis] += 1;
+            if next[axis] < self.shape[axis] {
+                self.next_index = Some(next);
+                break;
+            }
+            next[axis] = 0;
+        }
+        Some(current)
+    }
+}
+
+impl<T: Copy, const N: usize> NdArray<T, N> {
+
+    pub fn get(&self, index: [usize; N]) -> T {
+        self.array.get_unchecked(self.index_for(index))
+    }
+
+    pub fn try_get(&self, index: [usize; N]) -> Result<T, MemoryStructuresError> {
+        Ok(self.array.get_unchecked(self.try_index_for(index)?))
+    }
+
+    pub fn get_unchecked(&self, index: [usize; N]) -> T {
+        self.array.get_unchecked(self.unchecked_index_for(index))
+    }
+
+    /// Replaces every element of this NdArray with the result of the given function, which is called with
+    /// the element and the element of other that it is aligned with after broadcasting other to the shape
+    /// of this NdArray. The elements are copied, since other can share its elements with this NdArray
+    /// (for instance when a row of a matrix is added to the same matrix).
+    fn for_each_broadcast<F: FnMut(T, T) -> T>(&self, other: &NdArray<T, N>, mut function: F) -> Result<(), MemoryStructuresError> {
+        let other_strides = other.broadcast_strides(self.shape)?;
+        for index in self.indices() {
+            let other_index = other.offset + index.iter().zip(other_strides.iter())
+                .map(|(position, stride)| position * stride).sum::<usize>();
+            let other_element = other.array.get_unchecked(other_index);
+            self.set_unchecked(index, function(self.get_unchecked(index), other_element));
+        }
+        Ok(())
+    }
+
+    /// Sets all elements of this NdArray to the given value.
+    pub fn fill(&self, value: T){
+        for index in self.indices() {
+            self.set_unchecked(index, value);
+        }
+    }
+
+    /// Copies the elements of other into this NdArray, after broadcasting other to the shape of this
+    /// NdArray. This will panic if other can't be broadcast to the shape of this NdArray.
+    pub fn assign_broadcast(&self, other: &NdArray<T, N>){
+        if let Err(error) = self.try_assign_broadcast(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Copies the elements of other into this NdArray, just like assign_broadcast.
+    /// Instead of panicking, this will return an error.
+    pub fn try_assign_broadcast(&self, other: &NdArray<T, N>) -> Result<(), MemoryStructuresError> {
+        self.for_each_broadcast(other, |_, other_element| other_element)
+    }
+}
+
+impl<T: AddAssign + Copy, const N: usize> NdArray<T, N> {
+
+    /// Increases all elements of this NdArray by the given amount.
+    pub fn add_all(&self, amount: T){
+        for index in self.indices() {
+            *self.get_unchecked_mut_ref(index) += amount;
+        }
+    }
+
+    /// Increases every element of this NdArray by the corresponding element of other, after
+    /// broadcasting other to the shape of this NdArray. This will panic if other can't be broadcast
+    /// to the shape of this NdArray.
+    pub fn add_broadcast(&self, other: &NdArray<T, N>){
+        if let Err(error) = self.try_add_broadcast(other) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Increases every element of this NdArray by the corresponding element of other, just like
+    /// add_broadcast. Instead of panicking, this will return an error.
+    pub fn try_add_broadcast(&self, other: &NdArray<T, N>) -> Result<(), MemoryStructuresError> {
+        self.for_each_broadcast(other, |mut element, other_element| {
+            element += other_element;
+            element
+        })
+    }
+}
@@ -0,0 +1,230 @@
+//! Parallel operations on Arrays and Tables that are backed by rayon. This module is only
+//! available when the rayon feature of this crate is enabled.
+
+use crate::Array;
+use crate::Table;
+use crate::TableIndexing;
+
+use rayon::prelude::*;
+
+use std::borrow::Cow;
+use std::ops::{AddAssign, Deref, DerefMut};
+use std::slice;
+
+impl<T: Sync> Array<T> {
+
+    /// Creates a parallel iterator over references to all elements of this Array.
+    pub fn par_iter(&self) -> rayon::slice::Iter<'_, T> {
+        self.as_slice().par_iter()
+    }
+}
+
+impl<T: Send> Array<T> {
+
+    /// Creates a parallel iterator over mutable references to all elements of this Array.
+    pub fn par_iter_mut(&mut self) -> rayon::slice::IterMut<'_, T> {
+        self.as_mut_slice().par_iter_mut()
+    }
+}
+
+impl<T: Copy + Send + Sync> Array<T> {
+
+    /// Sets all elements in this Array to (a copy of) the specified value, using all threads of
+    /// the rayon thread pool.
+    pub fn par_set_all(&self, value: T){
+        self.as_mut_slice().par_iter_mut().for_each(|element| *element = value);
+    }
+
+    /// Replaces every element in this Array with the result of applying the given function to it,
+    /// using all threads of the rayon thread pool.
+    pub fn par_map_in_place<F: Fn(T) -> T + Sync + Send>(&self, function: F){
+        self.as_mut_slice().par_iter_mut().for_each(|element| *element = function(*element));
+    }
+}
+
+impl<T: AddAssign + Copy + Send + Sync> Array<T> {
+
+    /// Increases all elements in this Array by the specified amount, using all threads of the
+    /// rayon thread pool.
+    pub fn par_add_all(&self, amount: T){
+        self.as_mut_slice().par_iter_mut().for_each(|element| *element += amount);
+    }
+}
+
+/// A pointer that can be shared between the threads of the rayon thread pool. It is only used
+/// to access distinct elements of a table from different threads.
+struct SharedPointer<T>(*mut T);
+
+unsafe impl<T> Send for SharedPointer<T> {}
+unsafe impl<T> Sync for SharedPointer<T> {}
+
+impl<T> Clone for SharedPointer<T> {
+    fn clone(&self) -> SharedPointer<T> {
+        SharedPointer(self.0)
+    }
+}
+
+/// The position of a table in its backing Array, which can be copied into the closures of parallel iterators
+#[derive(Clone)]
+struct SharedIndexing<T> {
+    pointer: SharedPointer<T>,
+    offset: usize,
+    indexing: TableIndexing,
+    width: usize,
+    contiguous_rows: bool
+}
+
+impl<T> SharedIndexing<T> {
+
+    /// Gets a pointer to the element at (x, y) of the table
+    fn element(&self, x: usize, y: usize) -> *mut T {
+        unsafe { self.pointer.0.add(self.offset + self.indexing.index_for(x, y)) }
+    }
+
+    /// Gets the elements of row y of the table, which must have contiguous rows. The slice only covers
+    /// get_width() elements, so it never covers the elements between the rows, which may belong to other
+    /// views on the same Array.
+    unsafe fn row<'a>(&self, y: usize) -> &'a [T] {
+        slice::from_raw_parts(self.element(0, y), self.width)
+    }
+
+    /// Gets the elements of row y of the table mutably, just like row
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn row_mut<'a>(&self, y: usize) -> &'a mut [T] {
+        slice::from_raw_parts_mut(self.element(0, y), self.width)
+    }
+}
+
+/// A row of a Table that is produced by par_rows_mut. It dereferences to a mutable slice with the
+/// get_width() elements of the row. When the elements of the row are not stored after each other in
+/// the backing Array, they are copied to a buffer, which is copied back into the table when the row
+/// is dropped.
+pub struct TableRowMut<'a, T> {
+    row: RowStorage<'a, T>
+}
+
+enum RowStorage<'a, T> {
+    Slice(&'a mut [T]),
+    Buffer { elements: Vec<T>, table: SharedIndexing<T>, y: usize }
+}
+
+impl<'a, T> Deref for TableRowMut<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.row {
+            RowStorage::Slice(row) => row,
+            RowStorage::Buffer { elements, .. } => elements
+        }
+    }
+}
+
+impl<'a, T> DerefMut for TableRowMut<'a, T> {
+
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.row {
+            RowStorage::Slice(row) => row,
+            RowStorage::Buffer { elements, .. } => elements
+        }
+    }
+}
+
+impl<'a, T> Drop for TableRowMut<'a, T> {
+
+    fn drop(&mut self){
+        if let RowStorage::Buffer { elements, table, y } = &mut self.row {
+            for (x, element) in elements.drain(..).enumerate() {
+                // Only the thread that owns this row can access its elements
+                unsafe { *table.element(x, *y) = element; }
+            }
+        }
+    }
+}
+
+impl<T> Table<T> {
+
+    fn shared_indexing(&self) -> SharedIndexing<T> {
+        SharedIndexing {
+            pointer: SharedPointer(self.get_array().as_mut_ptr()),
+            offset: self.get_offset(),
+            indexing: self.get_indexing(),
+            width: self.get_width(),
+            contiguous_rows: self.has_contiguous_rows()
+        }
+    }
+
+    /// Applies the given function to all elements of this table, using all threads of the rayon thread pool.
+    fn par_for_each_element<F: Fn(&mut T) + Sync + Send>(&self, function: F) where T: Send {
+        let table = self.shared_indexing();
+        let table = &table;
+        (0..self.get_height()).into_par_iter().for_each(|y| {
+            // The indexing of a table never maps different coordinates to the same index,
+            // so no element will be modified by more than 1 thread
+            if table.contiguous_rows {
+                unsafe { table.row_mut(y) }.iter_mut().for_each(&function);
+            } else {
+                (0..table.width).for_each(|x| function(unsafe { &mut *table.element(x, y) }));
+            }
+        });
+    }
+}
+
+impl<T: Clone + Send + Sync> Table<T> {
+
+    /// Creates a parallel iterator over the rows of this table. Each row contains get_width() elements,
+    /// and the rows are produced from top to bottom. When the elements of the rows are stored after
+    /// each other (when the column stride is 1), the rows borrow the elements of this table. Otherwise,
+    /// the elements of each row are cloned into a Vec.
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = Cow<'_, [T]>> {
+        let table = self.shared_indexing();
+        (0..self.get_height()).into_par_iter().map(move |y| if table.contiguous_rows {
+            Cow::Borrowed(unsafe { table.row(y) })
+        } else {
+            Cow::Owned((0..table.width).map(|x| unsafe { &*table.element(x, y) }.clone()).collect())
+        })
+    }
+}
+
+impl<T: Clone + Send> Table<T> {
+
+    /// Creates a parallel iterator over mutable rows of this table. Each row contains get_width()
+    /// elements, and the rows are produced from top to bottom. When the elements of the rows are not
+    /// stored after each other (when the column stride is not 1), the elements of each row are cloned
+    /// into a buffer that is written back when the row is dropped.
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = TableRowMut<'_, T>> {
+        // This method borrows this table mutably and its rows are disjoint, so the rows can't alias
+        let table = self.shared_indexing();
+        (0..self.get_height()).into_par_iter().map(move |y| TableRowMut {
+            row: if table.contiguous_rows {
+                RowStorage::Slice(unsafe { table.row_mut(y) })
+            } else {
+                let elements = (0..table.width).map(|x| unsafe { &*table.element(x, y) }.clone()).collect();
+                RowStorage::Buffer { elements, table: table.clone(), y }
+            }
+        })
+    }
+}
+
+impl<T: Copy + Send + Sync> Table<T> {
+
+    /// Sets all elements in this table to (a copy of) the specified value, using all threads of
+    /// the rayon thread pool.
+    pub fn par_set_all(&self, value: T){
+        self.par_for_each_element(|element| *element = value);
+    }
+
+    /// Replaces every element in this table with the result of applying the given function to it,
+    /// using all threads of the rayon thread pool.
+    pub fn par_map_in_place<F: Fn(T) -> T + Sync + Send>(&self, function: F){
+        self.par_for_each_element(|element| *element = function(*element));
+    }
+}
+
+impl<T: AddAssign + Copy + Send + Sync> Table<T> {
+
+    /// Increases all elements in this table by the specified amount, using all threads of the
+    /// rayon thread pool.
+    pub fn par_add_all(&self, amount: T){
+        self.par_for_each_element(|element| *element += amount);
+    }
+}
@@ -0,0 +1,330 @@
+//! Reductions (sums, minima, maxima and means) and prefix scans on Arrays. Every operation has a
+//! variant for the whole Array and a _some variant for the elements at indices start_index (inclusive)
+//! to start_index + amount_of_elements (exclusive), just like add_some. The plain sums and scans use
+//! the + operator, which panics on integer overflow in debug builds and wraps in release builds. The
+//! saturating_ variants use the Saturating trait instead.
+
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::utility::{Saturating, ToF64};
+
+use std::ops::Add;
+
+/// Combines all elements of the slice, starting with the given initial value
+fn fold_slice<T: Copy, F: Fn(T, T) -> T>(slice: &[T], initial: T, combine: F) -> T {
+    slice.iter().fold(initial, |accumulator, element| combine(accumulator, *element))
+}
+
+/// Replaces every element of the slice with the combination of the initial value and all elements
+/// before it, including the element itself if inclusive is true.
+fn scan_slice<T: Copy, F: Fn(T, T) -> T>(slice: &mut [T], initial: T, inclusive: bool, combine: F){
+    let mut accumulator = initial;
+    for element in slice.iter_mut() {
+        let next = combine(accumulator, *element);
+        *element = if inclusive { next } else { accumulator };
+        accumulator = next;
+    }
+}
+
+impl<T: Copy> Array<T> {
+
+    /// Gets a mutable slice with the elements of the range, which must be within bounds. An empty range
+    /// is accepted for any start_index, so it gives an empty slice without slicing.
+    #[allow(clippy::mut_from_ref)]
+    fn range_slice(&self, start_index: usize, amount_of_elements: usize) -> &mut [T] {
+        if amount_of_elements == 0 {
+            return &mut [];
+        }
+        &mut self.as_mut_slice()[start_index..start_index + amount_of_elements]
+    }
+
+    /// Checks that the range is within bounds and not empty, which is required to find a minimum or maximum
+    fn try_check_non_empty_range(&self, start_index: usize, amount_of_elements: usize) -> Result<(), MemoryStructuresError> {
+        if amount_of_elements == 0 {
+            return Err(MemoryStructuresError::ZeroSize);
+        }
+        self.try_check_range(start_index, amount_of_elements)
+    }
+
+    /// Creates a new Array with copies of the elements of this Array and applies the given operation on it
+    fn transformed_copy<F: FnOnce(&Array<T>)>(&self, operation: F) -> Array<T> {
+        let copy = Array::from_vec(self.as_slice().to_vec());
+        operation(&copy);
+        copy
+    }
+}
+
+impl<T: Add<Output = T> + Default + Copy> Array<T> {
+
+    /// Computes the sum of all elements in this Array.
+    pub fn sum(&self) -> T {
+        fold_slice(self.as_slice(), T::default(), |a, b| a + b)
+    }
+
+    /// Computes the sum of some elements in this Array. This will panic if the range is out of bounds.
+    pub fn sum_some(&self, start_index: usize, amount_of_elements: usize) -> T {
+        match self.try_sum_some(start_index, amount_of_elements) {
+            Ok(sum) => sum,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the sum of some elements in this Array, just like sum_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_sum_some(&self, start_index: usize, amount_of_elements: usize) -> Result<T, MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        Ok(fold_slice(self.range_slice(start_index, amount_of_elements), T::default(), |a, b| a + b))
+    }
+
+    /// Replaces every element of this Array with the sum of itself and all elements before it.
+    pub fn inclusive_scan_in_place(&self){
+        scan_slice(self.as_mut_slice(), T::default(), true, |a, b| a + b);
+    }
+
+    /// Replaces every element of this Array with the sum of all elements before it. The first element
+    /// becomes 0 (the default value of T).
+    pub fn exclusive_scan_in_place(&self){
+        scan_slice(self.as_mut_slice(), T::default(), false, |a, b| a + b);
+    }
+
+    /// Performs an inclusive scan on some elements of this Array: every element in the range is replaced
+    /// with the sum of itself and all elements before it in the range. This will panic if the range is
+    /// out of bounds.
+    pub fn inclusive_scan_some(&self, start_index: usize, amount_of_elements: usize){
+        if let Err(error) = self.try_inclusive_scan_some(start_index, amount_of_elements) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs an inclusive scan on some elements of this Array, just like inclusive_scan_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_inclusive_scan_some(&self, start_index: usize, amount_of_elements: usize) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        scan_slice(self.range_slice(start_index, amount_of_elements), T::default(), true, |a, b| a + b);
+        Ok(())
+    }
+
+    /// Performs an exclusive scan on some elements of this Array: every element in the range is replaced
+    /// with the sum of all elements before it in the range. This will panic if the range is out of bounds.
+    pub fn exclusive_scan_some(&self, start_index: usize, amount_of_elements: usize){
+        if let Err(error) = self.try_exclusive_scan_some(start_index, amount_of_elements) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs an exclusive scan on some elements of this Array, just like exclusive_scan_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_exclusive_scan_some(&self, start_index: usize, amount_of_elements: usize) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        scan_slice(self.range_slice(start_index, amount_of_elements), T::default(), false, |a, b| a + b);
+        Ok(())
+    }
+
+    /// Creates a new Array containing the inclusive prefix sums of this Array, which is not modified.
+    pub fn inclusive_scan(&self) -> Array<T> {
+        self.transformed_copy(|copy| copy.inclusive_scan_in_place())
+    }
+
+    /// Creates a new Array containing the exclusive prefix sums of this Array, which is not modified.
+    pub fn exclusive_scan(&self) -> Array<T> {
+        self.transformed_copy(|copy| copy.exclusive_scan_in_place())
+    }
+}
+
+impl<T: Saturating + Default + Copy> Array<T> {
+
+    /// Computes the sum of all elements in this Array, using saturating additions.
+    pub fn saturating_sum(&self) -> T {
+        fold_slice(self.as_slice(), T::default(), |a, b| a.saturating_add(b))
+    }
+
+    /// Computes the sum of some elements in this Array, using saturating additions.
+    /// This will panic if the range is out of bounds.
+    pub fn saturating_sum_some(&self, start_index: usize, amount_of_elements: usize) -> T {
+        match self.try_saturating_sum_some(start_index, amount_of_elements) {
+            Ok(sum) => sum,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the sum of some elements in this Array, just like saturating_sum_some.
+    /// Instead of panicking, this method will return an error if the range is out of bounds.
+    pub fn try_saturating_sum_some(&self, start_index: usize, amount_of_elements: usize) -> Result<T, MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        Ok(fold_slice(self.range_slice(start_index, amount_of_elements), T::default(), |a, b| a.saturating_add(b)))
+    }
+
+    /// Performs an inclusive scan on this Array, just like inclusive_scan_in_place, using saturating additions.
+    pub fn saturating_inclusive_scan_in_place(&self){
+        scan_slice(self.as_mut_slice(), T::default(), true, |a, b| a.saturating_add(b));
+    }
+
+    /// Performs an exclusive scan on this Array, just like exclusive_scan_in_place, using saturating additions.
+    pub fn saturating_exclusive_scan_in_place(&self){
+        scan_slice(self.as_mut_slice(), T::default(), false, |a, b| a.saturating_add(b));
+    }
+
+    /// Performs an inclusive scan on some elements of this Array, just like inclusive_scan_some, using
+    /// saturating additions. This will panic if the range is out of bounds.
+    pub fn saturating_inclusive_scan_some(&self, start_index: usize, amount_of_elements: usize){
+        if let Err(error) = self.try_saturating_inclusive_scan_some(start_index, amount_of_elements) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs a saturating inclusive scan on some elements of this Array, just like
+    /// saturating_inclusive_scan_some. Instead of panicking, this method will return an error if the
+    /// range is out of bounds.
+    pub fn try_saturating_inclusive_scan_some(&self, start_index: usize, amount_of_elements: usize) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        scan_slice(self.range_slice(start_index, amount_of_elements), T::default(), true, |a, b| a.saturating_add(b));
+        Ok(())
+    }
+
+    /// Performs an exclusive scan on some elements of this Array, just like exclusive_scan_some, using
+    /// saturating additions. This will panic if the range is out of bounds.
+    pub fn saturating_exclusive_scan_some(&self, start_index: usize, amount_of_elements: usize){
+        if let Err(error) = self.try_saturating_exclusive_scan_some(start_index, amount_of_elements) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Performs a saturating exclusive scan on some elements of this Array, just like
+    /// saturating_exclusive_scan_some. Instead of panicking, this method will return an error if the
+    /// range is out of bounds.
+    pub fn try_saturating_exclusive_scan_some(&self, start_index: usize, amount_of_elements: usize) -> Result<(), MemoryStructuresError> {
+        self.try_check_range(start_index, amount_of_elements)?;
+        scan_slice(self.range_slice(start_index, amount_of_elements), T::default(), false, |a, b| a.saturating_add(b));
+        Ok(())
+    }
+
+    /// Creates a new Array containing the saturating inclusive prefix sums of this Array.
+    pub fn saturating_inclusive_scan(&self) -> Array<T> {
+        self.transformed_copy(|copy| copy.saturating_inclusive_scan_in_place())
+    }
+
+    /// Creates a new Array containing the saturating exclusive prefix sums of this Array.
+    pub fn saturating_exclusive_scan(&self) -> Array<T> {
+        self.transformed_copy(|copy| copy.saturating_exclusive_scan_in_place())
+    }
+}
+
+impl<T: PartialOrd + Copy> Array<T> {
+
+    /// Finds the index of the first element in the range that should be preferred over all elements
+    /// before it. The range must be valid and non-empty.
+    fn select_index<F: Fn(&T, &T) -> bool>(&self, start_index: usize, amount_of_elements: usize, prefer: F) -> usize {
+        let slice = self.range_slice(start_index, amount_of_elements);
+        let mut best_index = 0;
+        for (index, element) in slice.iter().enumerate().skip(1) {
+            if prefer(element, &slice[best_index]) {
+                best_index = index;
+            }
+        }
+        start_index + best_index
+    }
+
+    /// Gets the index of the smallest element in this Array. If there are multiple smallest elements,
+    /// the first one is chosen. Elements that can't be compared (like NaN) are only chosen when
+    /// they are the first element.
+    pub fn argmin(&self) -> usize {
+        self.select_index(0, self.len(), |a, b| a < b)
+    }
+
+    /// Gets the index of the largest element in this Array. If there are multiple largest elements,
+    /// the first one is chosen. Elements that can't be compared (like NaN) are only chosen when
+    /// they are the first element.
+    pub fn argmax(&self) -> usize {
+        self.select_index(0, self.len(), |a, b| a > b)
+    }
+
+    /// Gets the smallest element in this Array, as determined by argmin.
+    pub fn min(&self) -> T {
+        self.get_unchecked(self.argmin())
+    }
+
+    /// Gets the largest element in this Array, as determined by argmax.
+    pub fn max(&self) -> T {
+        self.get_unchecked(self.argmax())
+    }
+
+    /// Gets the index (in this Array) of the smallest element in the range. This will panic if the
+    /// range is empty or out of bounds.
+    pub fn argmin_some(&self, start_index: usize, amount_of_elements: usize) -> usize {
+        match self.try_argmin_some(start_index, amount_of_elements) {
+            Ok(index) => index,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the index of the smallest element in the range, just like argmin_some.
+    /// Instead of panicking, this method will return an error if the range is empty or out of bounds.
+    pub fn try_argmin_some(&self, start_index: usize, amount_of_elements: usize) -> Result<usize, MemoryStructuresError> {
+        self.try_check_non_empty_range(start_index, amount_of_elements)?;
+        Ok(self.select_index(start_index, amount_of_elements, |a, b| a < b))
+    }
+
+    /// Gets the index (in this Array) of the largest element in the range. This will panic if the
+    /// range is empty or out of bounds.
+    pub fn argmax_some(&self, start_index: usize, amount_of_elements: usize) -> usize {
+        match self.try_argmax_some(start_index, amount_of_elements) {
+            Ok(index) => index,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the index of the largest element in the range, just like argmax_some.
+    /// Instead of panicking, this method will return an error if the range is empty or out of bounds.
+    pub fn try_argmax_some(&self, start_index: usize, amount_of_elements: usize) -> Result<usize, MemoryStructuresError> {
+        self.try_check_non_empty_range(start_index, amount_of_elements)?;
+        Ok(self.select_index(start_index, amount_of_elements, |a, b| a > b))
+    }
+
+    /// Gets the smallest element in the range. This will panic if the range is empty or out of bounds.
+    pub fn min_some(&self, start_index: usize, amount_of_elements: usize) -> T {
+        self.get_unchecked(self.argmin_some(start_index, amount_of_elements))
+    }
+
+    /// Gets the smallest element in the range, just like min_some.
+    /// Instead of panicking, this method will return an error if the range is empty or out of bounds.
+    pub fn try_min_some(&self, start_index: usize, amount_of_elements: usize) -> Result<T, MemoryStructuresError> {
+        Ok(self.get_unchecked(self.try_argmin_some(start_index, amount_of_elements)?))
+    }
+
+    /// Gets the largest element in the range. This will panic if the range is empty or out of bounds.
+    pub fn max_some(&self, start_index: usize, amount_of_elements: usize) -> T {
+        self.get_unchecked(self.argmax_some(start_index, amount_of_elements))
+    }
+
+    /// Gets the largest element in the range, just like max_some.
+    /// Instead of panicking, this method will return an error if the range is empty or out of bounds.
+    pub fn try_max_some(&self, start_index: usize, amount_of_elements: usize) -> Result<T, MemoryStructuresError> {
+        Ok(self.get_unchecked(self.try_argmax_some(start_index, amount_of_elements)?))
+    }
+}
+
+impl<T: ToF64 + Copy> Array<T> {
+
+    /// Computes the average of all elements in this Array. The sum is computed with f64 values,
+    /// so this can't overflow.
+    pub fn mean(&self) -> f64 {
+        self.as_slice().iter().map(|element| element.to_f64()).sum::<f64>() / self.len() as f64
+    }
+
+    /// Computes the average of the elements in the range. This will panic if the range is empty or
+    /// out of bounds.
+    pub fn mean_some(&self, start_index: usize, amount_of_elements: usize) -> f64 {
+        match self.try_mean_some(start_index, amount_of_elements) {
+            Ok(mean) => mean,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the average of the elements in the range, just like mean_some.
+    /// Instead of panicking, this method will return an error if the range is empty or out of bounds.
+    pub fn try_mean_some(&self, start_index: usize, amount_of_elements: usize) -> Result<f64, MemoryStructuresError> {
+        self.try_check_non_empty_range(start_index, amount_of_elements)?;
+        let slice = self.range_slice(start_index, amount_of_elements);
+        Ok(slice.iter().map(|element| element.to_f64()).sum::<f64>() / amount_of_elements as f64)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+use crate::utility::{FromF64, ToF64};
+
+use std::cmp::min;
+
+/// The filter that Table::resize uses to compute the new elements. The element at (x, y) of a table
+/// is treated as a sample at the center of the cell from (x, y) to (x + 1, y + 1), so the corners of
+/// the original and resized table line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResizeFilter {
+
+    /// Use the element whose cell contains the center of the new element. This never creates values
+    /// that were not in the original table.
+    Nearest,
+
+    /// Interpolate linearly between the 2 nearest elements in both directions.
+    Bilinear,
+
+    /// Interpolate with a cubic (Catmull-Rom) spline through the 4 nearest elements in both directions.
+    /// This is sharper than bilinear interpolation, but can overshoot near sharp edges. For integer
+    /// types, the overshoot is clamped to the range of the type.
+    Bicubic,
+
+    /// Average all elements whose cells overlap the cell of the new element, weighted by the overlap.
+    /// This is the best filter for downsampling, since the other filters ignore most of the elements
+    /// when the table shrinks by more than a factor of 2.
+    Area
+}
+
+/// The weights of the source elements that contribute to a single element of the resized table
+type Weights = Vec<(usize, f64)>;
+
+impl ResizeFilter {
+
+    /// Computes for each coordinate along an axis of the resized table which source coordinates contribute
+    /// to it, and with which weights.
+    fn weights(&self, source_size: usize, destination_size: usize) -> Vec<Weights> {
+        let scale = source_size as f64 / destination_size as f64;
+        let last = source_size - 1;
+        (0..destination_size).map(|destination| {
+            let center = (destination as f64 + 0.5) * scale - 0.5;
+            match self {
+                ResizeFilter::Nearest => vec![(min((center + 0.5).floor().max(0.0) as usize, last), 1.0)],
+                ResizeFilter::Bilinear => {
+                    let center = center.clamp(0.0, last as f64);
+                    let lower = center.floor() as usize;
+                    let fraction = center - lower as f64;
+                    vec![(lower, 1.0 - fraction), (min(lower + 1, last), fraction)]
+                },
+                ResizeFilter::Bicubic => {
+                    let lower = center.floor();
+                    (-1..=2).map(|offset| {
+                        let position = lower + offset as f64;
+                        let index = position.clamp(0.0, last as f64) as usize;
+                        (index, catmull_rom(center - position))
+                    }).collect()
+                },
+                ResizeFilter::Area => {
+                    let start = destination as f64 * scale;
+                    let end = (destination + 1) as f64 * scale;
+                    let first = start.floor() as usize;
+                    let bound = min(end.ceil() as usize, source_size);
+                    (first..bound).map(|index| {
+                        let overlap = end.min(index as f64 + 1.0) - start.max(index as f64);
+                        (index, overlap / scale)
+                    }).filter(|(_, weight)| *weight > 0.0).collect()
+                }
+            }
+        }).collect()
+    }
+}
+
+/// The Catmull-Rom spline kernel, which is 1 at distance 0 and 0 at the other integer distances
+fn catmull_rom(distance: f64) -> f64 {
+    let distance = distance.abs();
+    if distance <= 1.0 {
+        (1.5 * distance - 2.5) * distance * distance + 1.0
+    } else if distance < 2.0 {
+        ((-0.5 * distance + 2.5) * distance - 4.0) * distance + 2.0
+    } else {
+        0.0
+    }
+}
+
+impl<T: ToF64 + FromF64 + Copy> Table<T> {
+
+    /// Creates a new table with the given width and height that contains this table scaled with the
+    /// given filter. The computations are done with f64 values, and the results are converted back
+    /// with FromF64 (which rounds and clamps for integer types). This will panic if the new width or
+    /// height is 0.
+    pub fn resize(&self, new_width: usize, new_height: usize, filter: ResizeFilter) -> Table<T> {
+        match self.try_resize(new_width, new_height, filter) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new table that contains this table scaled with the given filter, just like resize.
+    /// Instead of panicking, this will return an error if the new width or height is 0.
+    pub fn try_resize(&self, new_width: usize, new_height: usize, filter: ResizeFilter) -> Result<Table<T>, MemoryStructuresError> {
+        if new_width == 0 || new_height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width: new_width, height: new_height });
+        }
+        let size = new_width.checked_mul(new_height).ok_or(MemoryStructuresError::Overflow)?;
+        let rows_size = new_width.checked_mul(self.get_height()).ok_or(MemoryStructuresError::Overflow)?;
+        let weights_x = filter.weights(self.get_width(), new_width);
+        let weights_y = filter.weights(self.get_height(), new_height);
+
+        // All filters are separable, so first resize the rows and then the columns
+        let mut resized_rows = vec![0.0; rows_size];
+        for y in 0..self.get_height() {
+            for (x, weights) in weights_x.iter().enumerate() {
+                resized_rows[x + y * new_width] = weights.iter()
+                    .map(|(source_x, weight)| weight * self.get_unchecked(*source_x, y).to_f64()).sum();
+            }
+        }
+
+        let mut elements = Vec::with_capacity(size);
+        for weights in weights_y.iter() {
+            for x in 0..new_width {
+                let value: f64 = weights.iter().map(|(source_y, weight)| weight * resized_rows[x + source_y * new_width]).sum();
+                elements.push(T::from_f64(value));
+            }
+        }
+        Ok(Table::new(Array::from_vec(elements), new_width, new_height))
+    }
+}
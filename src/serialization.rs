@@ -0,0 +1,84 @@
+//! Serde support for Arrays and Tables. This module is only available when the serde feature of
+//! this crate is enabled.
+//!
+//! An Array is serialized as a sequence of its elements. A Table is serialized as a struct with a
+//! width, a height and the sequence of its elements, row by row. Sharing copies and sub tables
+//! serialize the elements they can see, and deserialization always creates Arrays and Tables that
+//! own their data.
+
+use crate::Array;
+use crate::Table;
+
+use serde::de::Error;
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: Serialize> Serialize for Array<T> {
+
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Array<T> {
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Array<T>, D::Error> {
+        let elements = Vec::deserialize(deserializer)?;
+        Array::try_from_vec(elements).map_err(D::Error::custom)
+    }
+}
+
+/// Serializes the elements of a table row by row, without copying them.
+struct TableElements<'a, T> {
+
+    table: &'a Table<T>
+}
+
+impl<'a, T: Serialize> Serialize for TableElements<'a, T> {
+
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let table = self.table;
+        let mut sequence = serializer.serialize_seq(Some(table.get_width() * table.get_height()))?;
+        for y in 0..table.get_height() {
+            for x in 0..table.get_width() {
+                sequence.serialize_element(table.get_unchecked_ref(x, y))?;
+            }
+        }
+        sequence.end()
+    }
+}
+
+impl<T: Serialize> Serialize for Table<T> {
+
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Table", 3)?;
+        state.serialize_field("width", &self.get_width())?;
+        state.serialize_field("height", &self.get_height())?;
+        state.serialize_field("elements", &TableElements { table: self })?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Table")]
+struct TableData<T> {
+
+    width: usize,
+    height: usize,
+    elements: Vec<T>
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Table<T> {
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Table<T>, D::Error> {
+        let data = TableData::deserialize(deserializer)?;
+        let expected_length = data.width.checked_mul(data.height).ok_or_else(|| D::Error::custom("The table is too large"))?;
+        if data.elements.len() != expected_length {
+            return Err(D::Error::custom(format!(
+                "The width is {} and the height is {}, but there are {} elements", data.width, data.height, data.elements.len()
+            )));
+        }
+        let array = Array::try_from_vec(data.elements).map_err(D::Error::custom)?;
+        Table::try_new(array, data.width, data.height).map_err(D::Error::custom)
+    }
+}
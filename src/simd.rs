@@ -0,0 +1,322 @@
+//! Explicitly vectorized implementations of the bulk operations of Array: set_all, set_some,
+//! add_all, add_some, add_unchecked_some, saturating_add_all and saturating_add_some (and thus
+//! also the Table and Volume operations that are built on them). These are used automatically
+//! when the element type is one of the primitive integer or float types; all other element types
+//! use the scalar loops of Array.
+//!
+//! On x86_64, the vectorized implementations use AVX2 or SSE2 instructions when the CPU supports
+//! them (which is detected at runtime). The elements that don't fit in a whole vector, and all
+//! elements on other architectures, are handled by a scalar fallback.
+//!
+//! The vector instructions can't detect integer overflow, so the integer additions of add_all, add_some
+//! and add_unchecked_some are only vectorized in release builds. In debug builds, they use the scalar
+//! loops of Array, which panic on overflow just like Array::add.
+
+use crate::utility::Saturating;
+
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+/// The instruction set that is used for the vectorized operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SimdPath {
+    Avx2,
+    Sse2,
+    Scalar
+}
+
+#[cfg(test)]
+thread_local! {
+    static FORCED_PATH: std::cell::Cell<Option<SimdPath>> = const { std::cell::Cell::new(None) };
+}
+
+impl SimdPath {
+
+    fn is_supported(self) -> bool {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            SimdPath::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            SimdPath::Sse2 => is_x86_feature_detected!("sse2"),
+            SimdPath::Scalar => true,
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false
+        }
+    }
+
+    /// The best path that the CPU supports, unless a test forced another path on this thread
+    fn detect() -> SimdPath {
+        #[cfg(test)]
+        if let Some(path) = FORCED_PATH.with(|forced| forced.get()) {
+            return path;
+        }
+        [SimdPath::Avx2, SimdPath::Sse2].iter().copied().find(|path| path.is_supported()).unwrap_or(SimdPath::Scalar)
+    }
+
+    /// Checks if the vectorized integer additions may be used. They wrap on overflow, so they are only
+    /// used in release builds, or when a test forced a path to check the vectorized implementations.
+    fn allows_wrapping_add() -> bool {
+        #[cfg(test)]
+        if FORCED_PATH.with(|forced| forced.get()).is_some() {
+            return true;
+        }
+        !cfg!(debug_assertions)
+    }
+
+    /// All paths that the CPU supports, which the tests use to check each of them
+    #[cfg(test)]
+    pub(crate) fn supported() -> Vec<SimdPath> {
+        [SimdPath::Avx2, SimdPath::Sse2, SimdPath::Scalar].iter().copied().filter(|path| path.is_supported()).collect()
+    }
+
+    /// Runs the operation with all vectorized operations on this thread using this path
+    #[cfg(test)]
+    pub(crate) fn force<R, F: FnOnce() -> R>(self, operation: F) -> R {
+        assert!(self.is_supported(), "{:?} is not supported by this CPU", self);
+        struct Reset;
+        impl Drop for Reset {
+            fn drop(&mut self){
+                FORCED_PATH.with(|forced| forced.set(None));
+            }
+        }
+        FORCED_PATH.with(|forced| forced.set(Some(self)));
+        let _reset = Reset;
+        operation()
+    }
+}
+
+/// Gets the TypeId of T without requiring T: 'static. TypeId ignores lifetimes, and the result is only
+/// compared with the TypeIds of primitive types, which don't have lifetimes.
+fn type_id_of<T: ?Sized>() -> TypeId {
+    trait NonStaticAny {
+        fn get_type_id(&self) -> TypeId where Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn get_type_id(&self) -> TypeId where Self: 'static {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom_data = PhantomData::<T>;
+    NonStaticAny::get_type_id(unsafe {
+        std::mem::transmute::<&dyn NonStaticAny, &(dyn NonStaticAny + 'static)>(&phantom_data)
+    })
+}
+
+/// Reinterprets the pointer and value as U if T is U
+fn cast<T: Copy, U: Copy + 'static>(pointer: *mut T, value: T) -> Option<(*mut U, U)> {
+    if type_id_of::<T>() == TypeId::of::<U>() {
+        Some((pointer as *mut U, unsafe { std::mem::transmute_copy::<T, U>(&value) }))
+    } else {
+        None
+    }
+}
+
+/// An element type for which vectorized fill and add implementations are available.
+/// Additions on integer types will wrap around on overflow (see SimdPath::allows_wrapping_add).
+trait SimdElement: Copy {
+
+    /// Sets the length elements starting at pointer to the given value. The elements don't need to be
+    /// initialized, but they must all be valid for writes.
+    unsafe fn fill(pointer: *mut Self, length: usize, value: Self, path: SimdPath);
+
+    /// Increases the length elements starting at pointer by the given amount.
+    unsafe fn add(pointer: *mut Self, length: usize, amount: Self, path: SimdPath);
+}
+
+/// An integer element type for which a vectorized saturating add implementation is available.
+trait SimdSaturating: SimdElement + Saturating {
+
+    /// Performs a saturating addition of the given amount on the length elements starting at pointer.
+    unsafe fn saturating_add(pointer: *mut Self, length: usize, amount: Self, path: SimdPath);
+}
+
+// Applies a vector operation on as many whole vectors as possible, using the given path. It returns
+// the number of elements that have been processed.
+macro_rules! vectorized_prefix {
+    ($path:expr, $pointer:expr, $length:expr, $value:expr, $t:ty, $cast:ty,
+     |$avx2_old:ident, $avx2_vector:ident| $avx2_operation:expr,
+     |$sse2_old:ident, $sse2_vector:ident| $sse2_operation:expr,
+     $load256:ident, $store256:ident, $set256:ident, $load128:ident, $store128:ident, $set128:ident) => {{
+
+        #[cfg(target_arch = "x86_64")]
+        let processed = {
+            use std::arch::x86_64::*;
+            use std::mem::size_of;
+
+            #[target_feature(enable = "avx2")]
+            unsafe fn process_avx2(pointer: *mut $t, length: usize, value: $t) -> usize {
+                let lanes = 32 / size_of::<$t>();
+                let $avx2_vector = $set256(value as $cast);
+                let amount_of_vectors = length / lanes;
+                for vector_index in 0..amount_of_vectors {
+                    let location = pointer.add(vector_index * lanes);
+                    let $avx2_old = $load256(location as _);
+                    $store256(location as _, $avx2_operation);
+                }
+                amount_of_vectors * lanes
+            }
+
+            #[target_feature(enable = "sse2")]
+            unsafe fn process_sse2(pointer: *mut $t, length: usize, value: $t) -> usize {
+                let lanes = 16 / size_of::<$t>();
+                let $sse2_vector = $set128(value as $cast);
+                let amount_of_vectors = length / lanes;
+                for vector_index in 0..amount_of_vectors {
+                    let location = pointer.add(vector_index * lanes);
+                    let $sse2_old = $load128(location as _);
+                    $store128(location as _, $sse2_operation);
+                }
+                amount_of_vectors * lanes
+            }
+
+            match $path {
+                SimdPath::Avx2 => process_avx2($pointer, $length, $value),
+                SimdPath::Sse2 => process_sse2($pointer, $length, $value),
+                SimdPath::Scalar => 0
+            }
+        };
+
+        #[cfg(not(target_arch = "x86_64"))]
+        let processed = {
+            let _ = ($path, $pointer, $length, $value);
+            0
+        };
+
+        processed
+    }};
+}
+
+macro_rules! impl_simd_element {
+    ($t:ty, $cast:ty, |$a:ident, $b:ident| $scalar_add:expr, $load256:ident, $store256:ident, $set256:ident, $add256:ident,
+     $load128:ident, $store128:ident, $set128:ident, $add128:ident) => {
+
+        impl SimdElement for $t {
+
+            unsafe fn fill(pointer: *mut $t, length: usize, value: $t, path: SimdPath){
+                let processed = vectorized_prefix!(
+                    path, pointer, length, value, $t, $cast, |_old, vector| vector, |_old, vector| vector,
+                    $load256, $store256, $set256, $load128, $store128, $set128
+                );
+                for index in processed..length {
+                    pointer.add(index).write(value);
+                }
+            }
+
+            unsafe fn add(pointer: *mut $t, length: usize, amount: $t, path: SimdPath){
+                let processed = vectorized_prefix!(
+                    path, pointer, length, amount, $t, $cast, |old, vector| $add256(old, vector), |old, vector| $add128(old, vector),
+                    $load256, $store256, $set256, $load128, $store128, $set128
+                );
+                for index in processed..length {
+                    let location = pointer.add(index);
+                    let $a = *location;
+                    let $b = amount;
+                    *location = $scalar_add;
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_simd_integer {
+    ($t:ty, $cast:ty, $set256:ident, $add256:ident, $set128:ident, $add128:ident) => {
+        impl_simd_element!(
+            $t, $cast, |a, b| a.wrapping_add(b), _mm256_loadu_si256, _mm256_storeu_si256, $set256, $add256,
+            _mm_loadu_si128, _mm_storeu_si128, $set128, $add128
+        );
+    };
+}
+
+impl_simd_integer!(u8, i8, _mm256_set1_epi8, _mm256_add_epi8, _mm_set1_epi8, _mm_add_epi8);
+impl_simd_integer!(i8, i8, _mm256_set1_epi8, _mm256_add_epi8, _mm_set1_epi8, _mm_add_epi8);
+impl_simd_integer!(u16, i16, _mm256_set1_epi16, _mm256_add_epi16, _mm_set1_epi16, _mm_add_epi16);
+impl_simd_integer!(i16, i16, _mm256_set1_epi16, _mm256_add_epi16, _mm_set1_epi16, _mm_add_epi16);
+impl_simd_integer!(u32, i32, _mm256_set1_epi32, _mm256_add_epi32, _mm_set1_epi32, _mm_add_epi32);
+impl_simd_integer!(i32, i32, _mm256_set1_epi32, _mm256_add_epi32, _mm_set1_epi32, _mm_add_epi32);
+impl_simd_integer!(u64, i64, _mm256_set1_epi64x, _mm256_add_epi64, _mm_set1_epi64x, _mm_add_epi64);
+impl_simd_integer!(i64, i64, _mm256_set1_epi64x, _mm256_add_epi64, _mm_set1_epi64x, _mm_add_epi64);
+
+impl_simd_element!(
+    f32, f32, |a, b| a + b, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_set1_ps, _mm256_add_ps,
+    _mm_loadu_ps, _mm_storeu_ps, _mm_set1_ps, _mm_add_ps
+);
+impl_simd_element!(
+    f64, f64, |a, b| a + b, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_set1_pd, _mm256_add_pd,
+    _mm_loadu_pd, _mm_storeu_pd, _mm_set1_pd, _mm_add_pd
+);
+
+// For 32-bit and 64-bit integers, x86_64 doesn't have saturating vector additions, so only the
+// 8-bit and 16-bit integers implement SimdSaturating.
+macro_rules! impl_simd_saturating {
+    ($t:ty, $cast:ty, $set256:ident, $adds256:ident, $set128:ident, $adds128:ident) => {
+        impl SimdSaturating for $t {
+
+            unsafe fn saturating_add(pointer: *mut $t, length: usize, amount: $t, path: SimdPath){
+                let processed = vectorized_prefix!(
+                    path, pointer, length, amount, $t, $cast, |old, vector| $adds256(old, vector), |old, vector| $adds128(old, vector),
+                    _mm256_loadu_si256, _mm256_storeu_si256, $set256, _mm_loadu_si128, _mm_storeu_si128, $set128
+                );
+                for index in processed..length {
+                    let location = pointer.add(index);
+                    *location = (*location).saturating_add(amount);
+                }
+            }
+        }
+    };
+}
+
+impl_simd_saturating!(u8, i8, _mm256_set1_epi8, _mm256_adds_epu8, _mm_set1_epi8, _mm_adds_epu8);
+impl_simd_saturating!(i8, i8, _mm256_set1_epi8, _mm256_adds_epi8, _mm_set1_epi8, _mm_adds_epi8);
+impl_simd_saturating!(u16, i16, _mm256_set1_epi16, _mm256_adds_epu16, _mm_set1_epi16, _mm_adds_epu16);
+impl_simd_saturating!(i16, i16, _mm256_set1_epi16, _mm256_adds_epi16, _mm_set1_epi16, _mm_adds_epi16);
+
+/// Sets the length elements starting at pointer to the given value if T has a vectorized implementation.
+/// Returns false (without doing anything) if T doesn't have one. The elements don't need to be
+/// initialized, but they must all be valid for writes.
+pub(crate) unsafe fn try_fill<T: Copy>(pointer: *mut T, length: usize, value: T) -> bool {
+    macro_rules! fill_as {
+        ($($u:ty),*) => {$(
+            if let Some((pointer, value)) = cast::<T, $u>(pointer, value) {
+                <$u as SimdElement>::fill(pointer, length, value, SimdPath::detect());
+                return true;
+            }
+        )*};
+    }
+    fill_as!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+    false
+}
+
+/// Increases the length elements starting at pointer by the given amount if T has a vectorized
+/// implementation that may be used. Returns false (without doing anything) otherwise.
+pub(crate) unsafe fn try_add<T: Copy>(pointer: *mut T, length: usize, amount: T) -> bool {
+    macro_rules! add_as {
+        ($($u:ty),*) => {$(
+            if let Some((pointer, amount)) = cast::<T, $u>(pointer, amount) {
+                <$u as SimdElement>::add(pointer, length, amount, SimdPath::detect());
+                return true;
+            }
+        )*};
+    }
+    add_as!(f32, f64);
+    if SimdPath::allows_wrapping_add() {
+        add_as!(u8, i8, u16, i16, u32, i32, u64, i64);
+    }
+    false
+}
+
+/// Performs a saturating addition of the given amount on the length elements starting at pointer if T
+/// has a vectorized implementation. Returns false (without doing anything) if T doesn't have one.
+pub(crate) unsafe fn try_saturating_add<T: Copy>(pointer: *mut T, length: usize, amount: T) -> bool {
+    macro_rules! saturating_add_as {
+        ($($u:ty),*) => {$(
+            if let Some((pointer, amount)) = cast::<T, $u>(pointer, amount) {
+                <$u as SimdSaturating>::saturating_add(pointer, length, amount, SimdPath::detect());
+                return true;
+            }
+        )*};
+    }
+    saturating_add_as!(u8, i8, u16, i16);
+    false
+}
@@ -0,0 +1,361 @@
+//! A compact binary format to save Arrays and Tables and load them again, possibly on another
+//! machine. All snapshots start with the following header:
+//!
+//! | Offset | Size | Field |
+//! |--------|------|-------|
+//! | 0      | 4    | The magic bytes `MSSN` |
+//! | 4      | 2    | The format version as little endian u16, currently 1 |
+//! | 6      | 1    | The byte order of all following values: 0 for little endian and 1 for big endian |
+//! | 7      | 1    | The element type tag (see SnapshotElement::TYPE_TAG) |
+//! | 8      | 1    | The number of dimensions: 1 for an Array and 2 for a Table |
+//! | 9      | 1    | Flags: bit 0 is set when the snapshot ends with a checksum |
+//! | 10     | 8 * dimensions | The dimensions as u64: the length of an Array, or the width and height of a Table |
+//!
+//! The header is followed by the elements, row by row in case of a Table. When bit 0 of the flags is
+//! set, the elements are followed by a u32 CRC-32 (IEEE) checksum of all preceding bytes, including
+//! the header. Snapshots are written in the byte order of the machine that writes them; readers swap
+//! the bytes when needed.
+
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+const MAGIC: [u8; 4] = *b"MSSN";
+const FORMAT_VERSION: u16 = 1;
+const FLAG_CHECKSUM: u8 = 1;
+
+/// The number of elements that is converted to or from bytes at once
+const CHUNK_SIZE: usize = 8192;
+
+/// An element type that can be stored in a snapshot. It is implemented for all integer types with
+/// a fixed size and for f32 and f64.
+pub trait SnapshotElement: Copy {
+
+    /// The tag that identifies this type in the snapshot header
+    const TYPE_TAG: u8;
+
+    /// The number of bytes that is needed to store a value of this type
+    const SIZE: usize;
+
+    /// Appends the bytes of this value to the given Vec, in the given byte order
+    fn write_bytes(self, big_endian: bool, bytes: &mut Vec<u8>);
+
+    /// Converts SIZE bytes in the given byte order to a value of this type
+    fn read_bytes(bytes: &[u8], big_endian: bool) -> Self;
+}
+
+macro_rules! impl_snapshot_element {
+    ($($t:ty => $tag:expr),*) => {
+        $(
+            impl SnapshotElement for $t {
+
+                const TYPE_TAG: u8 = $tag;
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn write_bytes(self, big_endian: bool, bytes: &mut Vec<u8>){
+                    if big_endian {
+                        bytes.extend_from_slice(&self.to_be_bytes());
+                    } else {
+                        bytes.extend_from_slice(&self.to_le_bytes());
+                    }
+                }
+
+                fn read_bytes(bytes: &[u8], big_endian: bool) -> $t {
+                    let mut array = [0; std::mem::size_of::<$t>()];
+                    array.copy_from_slice(bytes);
+                    if big_endian {
+                        <$t>::from_be_bytes(array)
+                    } else {
+                        <$t>::from_le_bytes(array)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_element!(
+    u8 => 1, i8 => 2, u16 => 3, i16 => 4, u32 => 5, i32 => 6, u64 => 7, i64 => 8,
+    u128 => 9, i128 => 10, f32 => 11, f64 => 12
+);
+
+/// The error type of the methods that save and load snapshots.
+#[derive(Debug)]
+pub enum SnapshotError {
+
+    /// Reading or writing failed
+    Io(io::Error),
+
+    /// The data doesn't start with the magic bytes of a snapshot
+    InvalidMagic([u8; 4]),
+
+    /// The snapshot was written with a format version that this version of the crate doesn't support
+    UnsupportedVersion(u16),
+
+    /// The byte order in the header is neither 0 nor 1
+    InvalidEndianness(u8),
+
+    /// The snapshot contains elements of another type than the requested type
+    WrongElementType { expected: u8, found: u8 },
+
+    /// The snapshot contains a structure with another number of dimensions than the requested structure
+    WrongDimensions { expected: u8, found: u8 },
+
+    /// The checksum at the end of the snapshot doesn't match its contents
+    ChecksumMismatch { expected: u32, computed: u32 },
+
+    /// The dimensions in the header don't describe a valid structure
+    InvalidStructure(MemoryStructuresError)
+}
+
+impl fmt::Display for SnapshotError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "I/O error: {}", error),
+            SnapshotError::InvalidMagic(magic) => write!(f, "The magic bytes are {:?}, but they should be {:?}", magic, MAGIC),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "The format version is {}, but only version {} is supported", version, FORMAT_VERSION)
+            },
+            SnapshotError::InvalidEndianness(endianness) => write!(f, "The endianness is {}, but it must be 0 or 1", endianness),
+            SnapshotError::WrongElementType { expected, found } => {
+                write!(f, "The element type tag is {}, but {} was expected", found, expected)
+            },
+            SnapshotError::WrongDimensions { expected, found } => {
+                write!(f, "The snapshot has {} dimensions, but {} were expected", found, expected)
+            },
+            SnapshotError::ChecksumMismatch { expected, computed } => {
+                write!(f, "The stored checksum is {:#010x}, but the computed checksum is {:#010x}", expected, computed)
+            },
+            SnapshotError::InvalidStructure(error) => write!(f, "Invalid structure: {}", error)
+        }
+    }
+}
+
+impl Error for SnapshotError {
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(error) => Some(error),
+            SnapshotError::InvalidStructure(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+
+    fn from(error: io::Error) -> SnapshotError {
+        SnapshotError::Io(error)
+    }
+}
+
+impl From<MemoryStructuresError> for SnapshotError {
+
+    fn from(error: MemoryStructuresError) -> SnapshotError {
+        SnapshotError::InvalidStructure(error)
+    }
+}
+
+const fn create_crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 != 0 { (value >> 1) ^ 0xEDB8_8320 } else { value >> 1 };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+}
+
+const CRC_TABLE: [u32; 256] = create_crc_table();
+
+/// Computes the CRC-32 (IEEE) checksum of all bytes that are passed to update
+struct Crc32 {
+
+    value: u32
+}
+
+impl Crc32 {
+
+    fn new() -> Crc32 {
+        Crc32 { value: 0xFFFF_FFFF }
+    }
+
+    fn update(&mut self, bytes: &[u8]){
+        for byte in bytes {
+            self.value = CRC_TABLE[((self.value ^ *byte as u32) & 0xFF) as usize] ^ (self.value >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.value
+    }
+}
+
+/// Writes the given bytes to the writer and includes them in the checksum
+fn write_checked<W: Write>(writer: &mut W, checksum: &mut Crc32, bytes: &[u8]) -> io::Result<()> {
+    checksum.update(bytes);
+    writer.write_all(bytes)
+}
+
+/// Reads exactly bytes.len() bytes from the reader and includes them in the checksum
+fn read_checked<R: Read>(reader: &mut R, checksum: &mut Crc32, bytes: &mut [u8]) -> io::Result<()> {
+    reader.read_exact(bytes)?;
+    checksum.update(bytes);
+    Ok(())
+}
+
+fn save_snapshot<T: SnapshotElement, W: Write>(
+    mut writer: W, dimensions: &[usize], include_checksum: bool, elements: impl Iterator<Item = T>
+) -> Result<(), SnapshotError> {
+    let big_endian = cfg!(target_endian = "big");
+    let mut checksum = Crc32::new();
+
+    let mut header = Vec::with_capacity(10 + 8 * dimensions.len());
+    header.extend_from_slice(&MAGIC);
+    header.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    header.push(big_endian as u8);
+    header.push(T::TYPE_TAG);
+    header.push(dimensions.len() as u8);
+    header.push(if include_checksum { FLAG_CHECKSUM } else { 0 });
+    for dimension in dimensions {
+        (*dimension as u64).write_bytes(big_endian, &mut header);
+    }
+    write_checked(&mut writer, &mut checksum, &header)?;
+
+    let mut bytes = Vec::with_capacity(CHUNK_SIZE * T::SIZE);
+    for element in elements {
+        element.write_bytes(big_endian, &mut bytes);
+        if bytes.len() >= CHUNK_SIZE * T::SIZE {
+            write_checked(&mut writer, &mut checksum, &bytes)?;
+            bytes.clear();
+        }
+    }
+    write_checked(&mut writer, &mut checksum, &bytes)?;
+
+    if include_checksum {
+        bytes.clear();
+        checksum.finish().write_bytes(big_endian, &mut bytes);
+        writer.write_all(&bytes)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads a snapshot with the given number of dimensions. The elements are stored in a new Array
+/// that is returned together with the dimensions.
+fn load_snapshot<T: SnapshotElement, R: Read>(mut reader: R, expected_dimensions: u8) -> Result<(Array<T>, Vec<usize>), SnapshotError> {
+    let mut checksum = Crc32::new();
+
+    let mut header = [0; 10];
+    read_checked(&mut reader, &mut checksum, &mut header)?;
+    let mut magic = [0; 4];
+    magic.copy_from_slice(&header[0..4]);
+    if magic != MAGIC {
+        return Err(SnapshotError::InvalidMagic(magic));
+    }
+    let version = u16::from_le_bytes([header[4], header[5]]);
+    if version != FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+    let big_endian = match header[6] {
+        0 => false,
+        1 => true,
+        endianness => return Err(SnapshotError::InvalidEndianness(endianness))
+    };
+    if header[7] != T::TYPE_TAG {
+        return Err(SnapshotError::WrongElementType { expected: T::TYPE_TAG, found: header[7] });
+    }
+    if header[8] != expected_dimensions {
+        return Err(SnapshotError::WrongDimensions { expected: expected_dimensions, found: header[8] });
+    }
+    let has_checksum = header[9] & FLAG_CHECKSUM != 0;
+
+    let mut dimensions = Vec::with_capacity(expected_dimensions as usize);
+    let mut size: usize = 1;
+    for _ in 0..expected_dimensions {
+        let mut dimension_bytes = [0; 8];
+        read_checked(&mut reader, &mut checksum, &mut dimension_bytes)?;
+        let dimension = u64::read_bytes(&dimension_bytes, big_endian);
+        if dimension > usize::MAX as u64 {
+            return Err(SnapshotError::InvalidStructure(MemoryStructuresError::Overflow));
+        }
+        let dimension = dimension as usize;
+        size = size.checked_mul(dimension).ok_or(MemoryStructuresError::Overflow)?;
+        dimensions.push(dimension);
+    }
+
+    size.checked_mul(T::SIZE).ok_or(MemoryStructuresError::Overflow)?;
+
+    // The size comes from the header, which could be corrupted, so the memory is only allocated
+    // while the elements are actually read
+    let mut elements = Vec::with_capacity(std::cmp::min(CHUNK_SIZE, size));
+    let mut bytes = vec![0; CHUNK_SIZE * T::SIZE];
+    while elements.len() < size {
+        let amount = std::cmp::min(CHUNK_SIZE, size - elements.len());
+        let chunk = &mut bytes[..amount * T::SIZE];
+        read_checked(&mut reader, &mut checksum, chunk)?;
+        elements.extend(chunk.chunks_exact(T::SIZE).map(|element_bytes| T::read_bytes(element_bytes, big_endian)));
+    }
+    let array = Array::try_from_vec(elements)?;
+
+    if has_checksum {
+        let mut checksum_bytes = [0; 4];
+        reader.read_exact(&mut checksum_bytes)?;
+        let expected = u32::read_bytes(&checksum_bytes, big_endian);
+        let computed = checksum.finish();
+        if expected != computed {
+            return Err(SnapshotError::ChecksumMismatch { expected, computed });
+        }
+    }
+    Ok((array, dimensions))
+}
+
+impl<T: SnapshotElement> Array<T> {
+
+    /// Writes a snapshot of the elements of this Array to the given writer. When include_checksum
+    /// is true, a checksum will be appended that allows load_from to verify that the snapshot is intact.
+    /// See the documentation of the snapshot module for a description of the format.
+    pub fn save_to<W: Write>(&self, writer: W, include_checksum: bool) -> Result<(), SnapshotError> {
+        save_snapshot(writer, &[self.len()], include_checksum, self.as_slice().iter().copied())
+    }
+
+    /// Reads an Array snapshot from the given reader and stores its elements in a new Array.
+    /// This will return an error if the snapshot is not a valid Array snapshot with elements of type T,
+    /// or if its checksum doesn't match.
+    pub fn load_from<R: Read>(reader: R) -> Result<Array<T>, SnapshotError> {
+        let (array, _) = load_snapshot(reader, 1)?;
+        Ok(array)
+    }
+}
+
+impl<T: SnapshotElement> Table<T> {
+
+    /// Writes a snapshot of the elements of this table to the given writer, row by row. When
+    /// include_checksum is true, a checksum will be appended that allows load_from to verify that
+    /// the snapshot is intact.
+    pub fn save_to<W: Write>(&self, writer: W, include_checksum: bool) -> Result<(), SnapshotError> {
+        let elements = (0..self.get_height()).flat_map(|y| {
+            (0..self.get_width()).map(move |x| self.get_unchecked(x, y))
+        });
+        save_snapshot(writer, &[self.get_width(), self.get_height()], include_checksum, elements)
+    }
+
+    /// Reads a Table snapshot from the given reader and stores its elements in a new Table.
+    /// This will return an error if the snapshot is not a valid Table snapshot with elements of type T,
+    /// or if its checksum doesn't match.
+    pub fn load_from<R: Read>(reader: R) -> Result<Table<T>, SnapshotError> {
+        let (array, dimensions) = load_snapshot(reader, 2)?;
+        Ok(Table::try_new(array, dimensions[0], dimensions[1])?)
+    }
+}
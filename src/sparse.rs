@@ -0,0 +1,237 @@
+use crate::Array;
+use crate::Graphics2D;
+use crate::MemoryStructuresError;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::ops::{Add, AddAssign};
+
+/// A 2-dimensional structure for huge tables whose elements are mostly equal to a default value. The
+/// table is divided in chunks of chunk_width by chunk_height elements, and a chunk is only allocated
+/// when one of its elements is modified. All elements of chunks that haven't been allocated are equal
+/// to the default value. Every chunk is an Array whose element at (local_x, local_y) is stored at index
+/// local_x + local_y * chunk_width. The chunks at the right and bottom edge can extend beyond the table;
+/// their elements outside the table always keep the default value.
+///
+/// Just like Table, all modifying methods only need a shared reference.
+pub struct SparseTable<T> {
+
+    chunks: RefCell<HashMap<(usize, usize), Array<T>>>,
+
+    width: usize,
+    height: usize,
+
+    chunk_width: usize,
+    chunk_height: usize,
+
+    default_value: T
+}
+
+impl<T: Copy> SparseTable<T> {
+
+    /// Creates a new SparseTable with the given width and height whose elements are all equal to the default
+    /// value. No chunks are allocated yet. This will panic if any of the dimensions is 0.
+    pub fn new(width: usize, height: usize, chunk_width: usize, chunk_height: usize, default_value: T) -> SparseTable<T> {
+        match SparseTable::try_new(width, height, chunk_width, chunk_height, default_value) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new SparseTable, just like new. Instead of panicking, this will return an error.
+    pub fn try_new(width: usize, height: usize, chunk_width: usize, chunk_height: usize, default_value: T) -> Result<SparseTable<T>, MemoryStructuresError> {
+        if width == 0 || height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
+        }
+        if chunk_width == 0 || chunk_height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width: chunk_width, height: chunk_height });
+        }
+        chunk_width.checked_mul(chunk_height).ok_or(MemoryStructuresError::Overflow)?;
+        Ok(SparseTable { chunks: RefCell::new(HashMap::new()), width, height, chunk_width, chunk_height, default_value })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_chunk_width(&self) -> usize {
+        self.chunk_width
+    }
+
+    pub fn get_chunk_height(&self) -> usize {
+        self.chunk_height
+    }
+
+    /// The value of all elements that are not in an allocated chunk
+    pub fn get_default_value(&self) -> T {
+        self.default_value
+    }
+
+    fn try_check_coordinates(&self, x: usize, y: usize) -> Result<(), MemoryStructuresError> {
+        if x >= self.width || y >= self.height {
+            return Err(MemoryStructuresError::CoordinatesOutOfBounds { x, y, width: self.width, height: self.height });
+        }
+        Ok(())
+    }
+
+    /// Gets the coordinates of the chunk that contains (x, y) and the index of (x, y) within that chunk
+    fn locate(&self, x: usize, y: usize) -> ((usize, usize), usize) {
+        let chunk = (x / self.chunk_width, y / self.chunk_height);
+        (chunk, x % self.chunk_width + (y % self.chunk_height) * self.chunk_width)
+    }
+
+    /// Calls consumer with the chunk at the given chunk coordinates, and allocates that chunk first if needed.
+    /// The chunks are only borrowed mutably to allocate the chunk, so existing chunks can be modified
+    /// while the chunks are borrowed by for_each_chunk.
+    fn with_chunk<R, F: FnOnce(&Array<T>) -> R>(&self, chunk: (usize, usize), consumer: F) -> R {
+        if !self.chunks.borrow().contains_key(&chunk) {
+            let array = Array::create_filled(self.chunk_width * self.chunk_height, self.default_value);
+            self.chunks.borrow_mut().insert(chunk, array);
+        }
+        consumer(&self.chunks.borrow()[&chunk])
+    }
+
+    /// Gets the element at (x, y). This will panic if x or y is outside this table.
+    pub fn get(&self, x: usize, y: usize) -> T {
+        match self.try_get(x, y) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the element at (x, y). This will return an error if x or y is outside this table.
+    pub fn try_get(&self, x: usize, y: usize) -> Result<T, MemoryStructuresError> {
+        self.try_check_coordinates(x, y)?;
+        Ok(self.get_unchecked(x, y))
+    }
+
+    /// Gets the element at (x, y). If x or y is outside this table, the result is unspecified.
+    pub fn get_unchecked(&self, x: usize, y: usize) -> T {
+        let (chunk, index) = self.locate(x, y);
+        match self.chunks.borrow().get(&chunk) {
+            Some(array) => array.get_unchecked(index),
+            None => self.default_value
+        }
+    }
+
+    /// Sets the element at (x, y) to the given value, and allocates its chunk if needed. This will
+    /// panic if x or y is outside this table.
+    pub fn set(&self, x: usize, y: usize, value: T){
+        if let Err(error) = self.try_set(x, y, value) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Sets the element at (x, y) to the given value, and allocates its chunk if needed. This will
+    /// return an error if x or y is outside this table.
+    pub fn try_set(&self, x: usize, y: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.try_check_coordinates(x, y)?;
+        self.set_unchecked(x, y, value);
+        Ok(())
+    }
+
+    /// Sets the element at (x, y) to the given value, and allocates its chunk if needed. If x or y is
+    /// outside this table, a chunk outside the table will be allocated and modified.
+    pub fn set_unchecked(&self, x: usize, y: usize, value: T){
+        let (chunk, index) = self.locate(x, y);
+        self.with_chunk(chunk, |array| array.set_unchecked(index, value));
+    }
+
+    /// The number of chunks that are currently allocated
+    pub fn get_chunk_count(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    /// Checks if the chunk with the given chunk coordinates is allocated. The chunk at (chunk_x, chunk_y)
+    /// contains the elements from (chunk_x * chunk_width, chunk_y * chunk_height) (inclusive) to
+    /// ((chunk_x + 1) * chunk_width, (chunk_y + 1) * chunk_height) (exclusive).
+    pub fn is_chunk_allocated(&self, chunk_x: usize, chunk_y: usize) -> bool {
+        self.chunks.borrow().contains_key(&(chunk_x, chunk_y))
+    }
+
+    /// Calls consumer with the chunk coordinates and the Array of every allocated chunk, sorted by chunk_y
+    /// and then by chunk_x. The consumer can modify the elements of the chunks, either through the given
+    /// Arrays or through the methods of this table. However, modifying an element of a chunk that is not
+    /// allocated (which would allocate it) or removing chunks will panic.
+    pub fn for_each_chunk<F: FnMut(usize, usize, &Array<T>)>(&self, mut consumer: F){
+        let chunks = self.chunks.borrow();
+        let mut coordinates: Vec<&(usize, usize)> = chunks.keys().collect();
+        coordinates.sort_unstable_by_key(|(chunk_x, chunk_y)| (*chunk_y, *chunk_x));
+        for chunk in coordinates {
+            consumer(chunk.0, chunk.1, &chunks[chunk]);
+        }
+    }
+
+    /// Deallocates the chunk with the given chunk coordinates, which resets all its elements to the
+    /// default value. Returns true if the chunk was allocated.
+    pub fn remove_chunk(&self, chunk_x: usize, chunk_y: usize) -> bool {
+        self.chunks.borrow_mut().remove(&(chunk_x, chunk_y)).is_some()
+    }
+
+    /// Deallocates all chunks, which resets all elements to the default value
+    pub fn clear(&self){
+        self.chunks.borrow_mut().clear();
+    }
+
+    /// The number of bytes used by the elements of the allocated chunks. The bookkeeping of the chunks
+    /// is not included.
+    pub fn get_memory_usage(&self) -> usize {
+        self.get_chunk_count() * self.chunk_width * self.chunk_height * size_of::<T>()
+    }
+
+    /// The number of bytes that a Table with the same width and height would need for its elements
+    pub fn get_dense_memory_usage(&self) -> usize {
+        self.width.saturating_mul(self.height).saturating_mul(size_of::<T>())
+    }
+}
+
+impl<T: PartialEq + Copy> SparseTable<T> {
+
+    /// Deallocates all chunks whose elements are all equal to the default value, which doesn't change
+    /// any element. Returns the number of chunks that were deallocated.
+    pub fn compact(&self) -> usize {
+        let mut chunks = self.chunks.borrow_mut();
+        let old_count = chunks.len();
+        chunks.retain(|_, array| array.as_slice().iter().any(|element| *element != self.default_value));
+        old_count - chunks.len()
+    }
+}
+
+impl<T: Add + AddAssign + Copy> Graphics2D<T> for SparseTable<T> {
+
+    fn add_unchecked(&self, x: usize, y: usize, amount: T){
+        let (chunk, index) = self.locate(x, y);
+        self.with_chunk(chunk, |array| array.add_unchecked(index, amount));
+    }
+
+    fn add_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, amount: T){
+        for chunk_y in min_y / self.chunk_height..=max_y / self.chunk_height {
+            let chunk_min_y = chunk_y * self.chunk_height;
+            let local_min_y = min_y.max(chunk_min_y) - chunk_min_y;
+            let local_max_y = max_y.min(chunk_min_y + self.chunk_height - 1) - chunk_min_y;
+            for chunk_x in min_x / self.chunk_width..=max_x / self.chunk_width {
+                let chunk_min_x = chunk_x * self.chunk_width;
+                let local_min_x = min_x.max(chunk_min_x) - chunk_min_x;
+                let local_max_x = max_x.min(chunk_min_x + self.chunk_width - 1) - chunk_min_x;
+                self.with_chunk((chunk_x, chunk_y), |array| {
+                    for local_y in local_min_y..=local_max_y {
+                        array.add_unchecked_some(local_min_x + local_y * self.chunk_width, local_max_x - local_min_x + 1, amount);
+                    }
+                });
+            }
+        }
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+}
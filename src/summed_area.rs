@@ -0,0 +1,309 @@
+use crate::Array;
+use crate::Graphics2D;
+use crate::MemoryStructuresError;
+use crate::Table;
+
+use std::ops::{Add, AddAssign, Sub};
+
+/// Checks that the rectangle from (min_x, min_y) to (max_x, max_y) (inclusive) is not empty and
+/// fits in a table with the given width and height.
+pub(crate) fn check_rect(min_x: usize, min_y: usize, max_x: usize, max_y: usize, width: usize, height: usize) -> Result<(), MemoryStructuresError> {
+    if min_x > max_x || min_y > max_y || max_x >= width || max_y >= height {
+        return Err(MemoryStructuresError::InvalidRect { min_x, min_y, max_x, max_y, width, height });
+    }
+    Ok(())
+}
+
+/// Stores for every (x, y) the sum of all elements of a Table in the rectangle from (0, 0) to (x, y),
+/// which makes it possible to compute the sum of any rectangle of the table in constant time. The
+/// summed-area table is not updated automatically when the table is modified: rebuild_region should
+/// be called after drawing on the table.
+///
+/// The default value of T is used as 0. The sums are stored as T, so T must be able to hold the sum of
+/// all elements of the table; otherwise the sums will overflow. For small integer types such as u8 and
+/// u16, new_widening can be used to store the sums in a wider type, for instance
+/// SummedAreaTable::<u64>::new_widening(&table).
+pub struct SummedAreaTable<T> {
+
+    /// The sum of the rectangle from (0, 0) to (x - 1, y - 1) is stored at (x, y). The first row and
+    /// column are always 0, which avoids special cases for rectangles at the edges.
+    sums: Table<T>
+}
+
+impl<T: Add<Output = T> + Sub<Output = T> + Default + Copy> SummedAreaTable<T> {
+
+    /// Creates a summed-area table for the current elements of the given table
+    pub fn new(table: &Table<T>) -> SummedAreaTable<T> {
+        SummedAreaTable::new_widening(table)
+    }
+
+    /// Creates a summed-area table for the current elements of the given table, whose elements are
+    /// converted to T before they are added. This will panic if the size of the summed-area table would
+    /// overflow.
+    pub fn new_widening<U: Into<T> + Copy>(table: &Table<U>) -> SummedAreaTable<T> {
+        let width = table.get_width() + 1;
+        let height = table.get_height() + 1;
+        let size = match width.checked_mul(height) {
+            Some(size) => size,
+            None => panic!("{}", MemoryStructuresError::Overflow)
+        };
+        let sums = Table::new(Array::create_filled(size, T::default()), width, height);
+        let summed_area_table = SummedAreaTable { sums };
+        summed_area_table.rebuild_unchecked_region(table, 0, 0);
+        summed_area_table
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.sums.get_width() - 1
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.sums.get_height() - 1
+    }
+
+    /// Recomputes all sums from the elements of the given table. This will panic if the table doesn't
+    /// have the same width and height as this summed-area table.
+    pub fn rebuild<U: Into<T> + Copy>(&self, table: &Table<U>){
+        self.rebuild_region(table, 0, 0);
+    }
+
+    /// Recomputes the sums that depend on the elements at x >= min_x and y >= min_y of the given table.
+    /// This should be called after modifying only elements in that part of the table, for instance
+    /// after drawing a rectangle or line whose top-left corner is (min_x, min_y). This is cheaper than
+    /// rebuild when the modified part is near the bottom-right corner. This will panic if the table
+    /// doesn't have the same width and height as this summed-area table or if (min_x, min_y) is outside it.
+    pub fn rebuild_region<U: Into<T> + Copy>(&self, table: &Table<U>, min_x: usize, min_y: usize){
+        if let Err(error) = self.try_rebuild_region(table, min_x, min_y) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Recomputes the sums that depend on the elements at x >= min_x and y >= min_y, just like
+    /// rebuild_region. Instead of panicking, this will return an error.
+    pub fn try_rebuild_region<U: Into<T> + Copy>(&self, table: &Table<U>, min_x: usize, min_y: usize) -> Result<(), MemoryStructuresError> {
+        if table.get_width() != self.get_width() || table.get_height() != self.get_height() {
+            return Err(MemoryStructuresError::DimensionMismatch {
+                expected_width: self.get_width(), expected_height: self.get_height(),
+                width: table.get_width(), height: table.get_height()
+            });
+        }
+        table.try_index_for(min_x, min_y)?;
+        self.rebuild_unchecked_region(table, min_x, min_y);
+        Ok(())
+    }
+
+    fn rebuild_unchecked_region<U: Into<T> + Copy>(&self, table: &Table<U>, min_x: usize, min_y: usize){
+        for y in min_y..table.get_height() {
+
+            // Accumulating the row sum avoids intermediate values that are larger than the final sums,
+            // which matters for unsigned integer types
+            let mut row_sum = self.sums.get_unchecked(min_x, y + 1) - self.sums.get_unchecked(min_x, y);
+            for x in min_x..table.get_width() {
+                row_sum = row_sum + table.get_unchecked(x, y).into();
+                self.sums.set_unchecked(x + 1, y + 1, self.sums.get_unchecked(x + 1, y) + row_sum);
+            }
+        }
+    }
+
+    /// Computes the sum of all elements in the rectangle from (min_x, min_y) to (max_x, max_y)
+    /// (inclusive). This will panic if the rectangle is empty or doesn't fit in the table.
+    pub fn rect_sum(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> T {
+        match self.try_rect_sum(min_x, min_y, max_x, max_y) {
+            Ok(sum) => sum,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the sum of all elements in a rectangle, just like rect_sum.
+    /// Instead of panicking, this will return an error.
+    pub fn try_rect_sum(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> Result<T, MemoryStructuresError> {
+        check_rect(min_x, min_y, max_x, max_y, self.get_width(), self.get_height())?;
+        Ok(self.rect_sum_unchecked(min_x, min_y, max_x, max_y))
+    }
+
+    pub fn rect_sum_unchecked(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> T {
+        let sum = |x, y| self.sums.get_unchecked(x, y);
+
+        // Both differences are sums of elements, so this doesn't underflow for unsigned integer types
+        (sum(max_x + 1, max_y + 1) - sum(min_x, max_y + 1)) - (sum(max_x + 1, min_y) - sum(min_x, min_y))
+    }
+}
+
+/// A 2-dimensional Fenwick tree (binary indexed tree), which supports adding to single elements and
+/// computing the sum of any rectangle in O(log(width) * log(height)) time. Unlike SummedAreaTable,
+/// this doesn't need to be rebuilt after modifications, so it is better suited for many point updates
+/// that are mixed with range queries.
+///
+/// The default value of T is used as 0.
+pub struct FenwickTable<T> {
+
+    /// The element at (x, y) contains the sum of the rectangle whose bottom-right corner is (x, y)
+    /// and whose size is determined by the lowest set bits of x + 1 and y + 1.
+    tree: Table<T>
+}
+
+/// Gets the value of the lowest bit of the given index that is 1
+fn lowest_bit(index: usize) -> usize {
+    index & index.wrapping_neg()
+}
+
+impl<T: Default + Copy> FenwickTable<T> {
+
+    /// Creates a new FenwickTable with the given width and height whose elements are all 0. This will
+    /// panic if the width or height is 0.
+    pub fn new(width: usize, height: usize) -> FenwickTable<T> {
+        match FenwickTable::try_new(width, height) {
+            Ok(fenwick_table) => fenwick_table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new FenwickTable with the given width and height, just like new.
+    /// Instead of panicking, this will return an error.
+    pub fn try_new(width: usize, height: usize) -> Result<FenwickTable<T>, MemoryStructuresError> {
+        let size = width.checked_mul(height).ok_or(MemoryStructuresError::Overflow)?;
+        if size == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
+        }
+        Ok(FenwickTable { tree: Table::new(Array::create_filled(size, T::default()), width, height) })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.tree.get_width()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.tree.get_height()
+    }
+}
+
+impl<T: AddAssign + Default + Copy> FenwickTable<T> {
+
+    /// Creates a new FenwickTable whose elements are equal to the current elements of the given table.
+    /// This takes linear time, which is faster than adding the elements one by one.
+    pub fn from_table(table: &Table<T>) -> FenwickTable<T> {
+        let fenwick_table = FenwickTable::new(table.get_width(), table.get_height());
+        let tree = &fenwick_table.tree;
+        let width = tree.get_width();
+        let height = tree.get_height();
+        for y in 0..height {
+            for x in 0..width {
+                tree.set_unchecked(x, y, table.get_unchecked(x, y));
+            }
+        }
+
+        // The 2-dimensional tree can be built by building a 1-dimensional tree along each axis
+        for y in 0..height {
+            for x in 0..width {
+                let parent_x = x + lowest_bit(x + 1);
+                if parent_x < width {
+                    *tree.get_unchecked_mut_ref(parent_x, y) += tree.get_unchecked(x, y);
+                }
+            }
+        }
+        for x in 0..width {
+            for y in 0..height {
+                let parent_y = y + lowest_bit(y + 1);
+                if parent_y < height {
+                    *tree.get_unchecked_mut_ref(x, parent_y) += tree.get_unchecked(x, y);
+                }
+            }
+        }
+        fenwick_table
+    }
+
+    /// Adds the given amount to the element at (x, y). This will panic if (x, y) is outside this table.
+    pub fn add(&self, x: usize, y: usize, amount: T){
+        self.tree.index_for(x, y);
+        self.add_unchecked(x, y, amount);
+    }
+
+    /// Adds the given amount to the element at (x, y), just like add.
+    /// Instead of panicking, this will return an error.
+    pub fn try_add(&self, x: usize, y: usize, amount: T) -> Result<(), MemoryStructuresError> {
+        self.tree.try_index_for(x, y)?;
+        self.add_unchecked(x, y, amount);
+        Ok(())
+    }
+
+    pub fn add_unchecked(&self, x: usize, y: usize, amount: T){
+        let mut tree_x = x + 1;
+        while tree_x <= self.get_width() {
+            let mut tree_y = y + 1;
+            while tree_y <= self.get_height() {
+                *self.tree.get_unchecked_mut_ref(tree_x - 1, tree_y - 1) += amount;
+                tree_y += lowest_bit(tree_y);
+            }
+            tree_x += lowest_bit(tree_x);
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Sub<Output = T> + Default + Copy> FenwickTable<T> {
+
+    /// Computes the sum of the rectangle that contains the first width columns and height rows
+    fn prefix_sum(&self, width: usize, height: usize) -> T {
+        let mut sum = T::default();
+        let mut tree_x = width;
+        while tree_x > 0 {
+            let mut tree_y = height;
+            while tree_y > 0 {
+                sum = sum + self.tree.get_unchecked(tree_x - 1, tree_y - 1);
+                tree_y -= lowest_bit(tree_y);
+            }
+            tree_x -= lowest_bit(tree_x);
+        }
+        sum
+    }
+
+    /// Computes the sum of all elements in the rectangle from (min_x, min_y) to (max_x, max_y)
+    /// (inclusive). This will panic if the rectangle is empty or doesn't fit in this table.
+    pub fn rect_sum(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> T {
+        match self.try_rect_sum(min_x, min_y, max_x, max_y) {
+            Ok(sum) => sum,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Computes the sum of all elements in a rectangle, just like rect_sum.
+    /// Instead of panicking, this will return an error.
+    pub fn try_rect_sum(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> Result<T, MemoryStructuresError> {
+        check_rect(min_x, min_y, max_x, max_y, self.get_width(), self.get_height())?;
+        Ok(self.rect_sum_unchecked(min_x, min_y, max_x, max_y))
+    }
+
+    pub fn rect_sum_unchecked(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize) -> T {
+        let sum = |width, height| self.prefix_sum(width, height);
+
+        // Both differences are sums of elements, so this doesn't underflow for unsigned integer types
+        (sum(max_x + 1, max_y + 1) - sum(min_x, max_y + 1)) - (sum(max_x + 1, min_y) - sum(min_x, min_y))
+    }
+
+    /// Gets the element at (x, y). This will panic if (x, y) is outside this table.
+    pub fn get(&self, x: usize, y: usize) -> T {
+        self.rect_sum(x, y, x, y)
+    }
+}
+
+impl<T: Add + AddAssign + Default + Copy> Graphics2D<T> for FenwickTable<T> {
+
+    fn add_unchecked(&self, x: usize, y: usize, amount: T){
+        FenwickTable::add_unchecked(self, x, y, amount);
+    }
+
+    fn add_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, amount: T){
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                FenwickTable::add_unchecked(self, x, y, amount);
+            }
+        }
+    }
+
+    fn get_width(&self) -> usize {
+        self.tree.get_width()
+    }
+
+    fn get_height(&self) -> usize {
+        self.tree.get_height()
+    }
+}
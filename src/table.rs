@@ -1,33 +1,216 @@
 use crate::Array;
 use crate::Graphics2D;
+use crate::MemoryStructuresError;
+use crate::TableLayout;
+use crate::TableView;
+use crate::layout::TableIndexing;
+
+use std::cmp::min;
 
 use std::ops::{Add,AddAssign};
 
+/// A 2-dimensional structure that is backed by an Array. Tables created with Table::new use the
+/// row-major layout: the element at (x, y) is stored at array index x + y * width. Strided tables
+/// store the element at (x, y) at array index offset + x * column_stride + y * row_stride. Sub
+/// tables (views on a part of another table) generally have a different offset and row stride,
+/// and slices of a Volume can also have a column stride that is larger than 1. Tables can also
+/// use the column-major, tiled and Morton layouts (see TableLayout).
 pub struct Table<T> {
 
     array: Array<T>,
 
     width: usize,
     height: usize,
-    bound: usize
+
+    offset: usize,
+    indexing: TableIndexing
 }
 
 impl<T> Table<T> {
 
     pub fn new(array: Array<T>, width: usize, height: usize) -> Table<T> {
+        match Table::try_new(array, width, height) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Table with the given width and height that is backed by the given Array.
+    /// Instead of panicking, this will return an error if the width or height is 0 or if the
+    /// array is too small.
+    pub fn try_new(array: Array<T>, width: usize, height: usize) -> Result<Table<T>, MemoryStructuresError> {
+        Table::try_with_stride(array, 0, width, height, width)
+    }
+
+    /// Creates a new Table whose element at (x, y) is stored at array index offset + x + y * row_stride.
+    /// This will panic if the width or height is 0, if the row stride is smaller than the width or if
+    /// the array is too small.
+    pub fn with_stride(array: Array<T>, offset: usize, width: usize, height: usize, row_stride: usize) -> Table<T> {
+        match Table::try_with_stride(array, offset, width, height, row_stride) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Table whose element at (x, y) is stored at array index offset + x + y * row_stride.
+    /// Instead of panicking, this will return an error if the width or height is 0, if the row stride
+    /// is smaller than the width or if the array is too small.
+    pub fn try_with_stride(array: Array<T>, offset: usize, width: usize, height: usize, row_stride: usize) -> Result<Table<T>, MemoryStructuresError> {
+        if width != 0 && height != 0 && row_stride < width {
+            return Err(MemoryStructuresError::InvalidStride { row_stride, width });
+        }
+        Table::try_with_strides(array, offset, width, height, 1, row_stride)
+    }
+
+    /// Creates a new Table whose element at (x, y) is stored at array index
+    /// offset + x * column_stride + y * row_stride. This will panic if the width or height is 0,
+    /// if the strides would cause different elements to share the same index or if the array is too small.
+    pub fn with_strides(array: Array<T>, offset: usize, width: usize, height: usize, column_stride: usize, row_stride: usize) -> Table<T> {
+        match Table::try_with_strides(array, offset, width, height, column_stride, row_stride) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Table whose element at (x, y) is stored at array index
+    /// offset + x * column_stride + y * row_stride. Instead of panicking, this will return an error
+    /// if the width or height is 0, if the strides would cause different elements to share the same
+    /// index or if the array is too small.
+    pub fn try_with_strides(
+        array: Array<T>, offset: usize, width: usize, height: usize, column_stride: usize, row_stride: usize
+    ) -> Result<Table<T>, MemoryStructuresError> {
         if width == 0 || height == 0 {
-            panic!("The width is {} and the height is {}, but neither can be 0", width, height);
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
         }
-        let bound = width.checked_mul(height).unwrap();
+
+        // Either the rows or the columns must be stored after each other without overlapping
+        let row_span = (width - 1).checked_mul(column_stride).and_then(|span| span.checked_add(1));
+        let column_span = (height - 1).checked_mul(row_stride).and_then(|span| span.checked_add(1));
+        let rows_separated = (width == 1 || column_stride != 0) && (height == 1 || row_span.is_some_and(|span| row_stride >= span));
+        let columns_separated = (height == 1 || row_stride != 0) && (width == 1 || column_span.is_some_and(|span| column_stride >= span));
+        if !rows_separated && !columns_separated {
+            return Err(MemoryStructuresError::OverlappingStrides { column_stride, row_stride, width, height });
+        }
+
+        let bound = (height - 1).checked_mul(row_stride)
+            .and_then(|last_row| (width - 1).checked_mul(column_stride).and_then(|last_column| last_row.checked_add(last_column)))
+            .and_then(|last_index| last_index.checked_add(offset))
+            .and_then(|last_index| last_index.checked_add(1))
+            .ok_or(MemoryStructuresError::Overflow)?;
 
         // This test ensures that any operation within the table bounds will also be within the Array bounds.
-        array.check_bound(bound - 1);
-        Table {
-            array: array,
-            width: width,
-            height: height,
-            bound: bound
+        if array.try_check_bound(bound - 1).is_err() {
+            return Err(MemoryStructuresError::ArrayTooSmall { required_size: bound, size: array.len() });
         }
+        Ok(Table {
+            array,
+            width,
+            height,
+            offset,
+            indexing: TableIndexing::Strided { column_stride, row_stride }
+        })
+    }
+
+    /// Creates a new Table with the given width and height that stores its elements in the given
+    /// Array using the given layout. The array needs at least layout.required_size(width, height)
+    /// elements. This will panic if the width or height is 0, if the layout is invalid or if the
+    /// array is too small.
+    pub fn with_layout(array: Array<T>, width: usize, height: usize, layout: TableLayout) -> Table<T> {
+        match Table::try_with_layout(array, width, height, layout) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Table with the given layout, just like with_layout.
+    /// Instead of panicking, this will return an error.
+    pub fn try_with_layout(array: Array<T>, width: usize, height: usize, layout: TableLayout) -> Result<Table<T>, MemoryStructuresError> {
+        let required_size = layout.try_required_size(width, height)?;
+
+        // Every layout maps the coordinates within the table to indices smaller than the required size
+        if array.len() < required_size {
+            return Err(MemoryStructuresError::ArrayTooSmall { required_size, size: array.len() });
+        }
+        Ok(Table {
+            array,
+            width,
+            height,
+            offset: 0,
+            indexing: layout.indexing(width, height)
+        })
+    }
+
+    /// Creates a Table that is a view on the rectangle of this table with its top-left corner at (x, y)
+    /// and the given width and height. The view shares its data with this table, so modifications
+    /// to the view will affect this table and vice versa. This will panic if the rectangle is empty
+    /// or doesn't fit in this table.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as Array::sharing_copy: the returned table must
+    /// not be used after the Array of this table has been dropped.
+    pub unsafe fn sub_table(&self, x: usize, y: usize, width: usize, height: usize) -> Table<T> {
+        match self.try_sub_table(x, y, width, height) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a Table that is a view on a rectangle of this table, just like sub_table.
+    /// Instead of panicking, this will return an error if the rectangle is empty or doesn't fit.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as Array::sharing_copy.
+    pub unsafe fn try_sub_table(&self, x: usize, y: usize, width: usize, height: usize) -> Result<Table<T>, MemoryStructuresError> {
+        if width == 0 || height == 0 {
+            return Err(MemoryStructuresError::ZeroDimension { width, height });
+        }
+        let fits_x = x.checked_add(width).is_some_and(|end_x| end_x <= self.width);
+        let fits_y = y.checked_add(height).is_some_and(|end_y| end_y <= self.height);
+        if !fits_x || !fits_y {
+            return Err(MemoryStructuresError::RegionOutOfBounds {
+                x, y, width, height, table_width: self.width, table_height: self.height
+            });
+        }
+        let (extra_offset, indexing) = self.indexing.shifted(x, y);
+        Ok(Table {
+            array: self.array.sharing_copy(),
+            width,
+            height,
+            offset: self.offset + extra_offset,
+            indexing
+        })
+    }
+
+    /// Splits this table into horizontal bands that contain rows_per_part rows each, except for the
+    /// last band, which may contain fewer rows. The returned views are disjoint and borrow this
+    /// table mutably, so they can safely be processed by different threads. This will panic if
+    /// rows_per_part is 0.
+    pub fn split_rows_mut(&mut self, rows_per_part: usize) -> Vec<TableView<'_, T>> {
+        let width = self.width;
+        self.split_tiles_mut(width, rows_per_part)
+    }
+
+    /// Splits this table into tiles of tile_width by tile_height elements. The tiles in the last
+    /// column and row may be smaller if the width and height are not multiples of the tile size.
+    /// The views are ordered row by row, starting at the top-left tile. They are disjoint and
+    /// borrow this table mutably, so they can safely be processed by different threads.
+    /// This will panic if tile_width or tile_height is 0.
+    pub fn split_tiles_mut(&mut self, tile_width: usize, tile_height: usize) -> Vec<TableView<'_, T>> {
+        if tile_width == 0 || tile_height == 0 {
+            panic!("The tile width is {} and the tile height is {}, but neither can be 0", tile_width, tile_height);
+        }
+        let mut views = Vec::new();
+        for y in (0..self.height).step_by(tile_height) {
+            for x in (0..self.width).step_by(tile_width) {
+                let width = min(tile_width, self.width - x);
+                let height = min(tile_height, self.height - y);
+
+                // The tiles are disjoint and the views borrow this table, so they can't outlive it
+                let tile = unsafe { self.sub_table(x, y, width, height) };
+                views.push(TableView::new(tile, x, y));
+            }
+        }
+        views
     }
 
     pub fn get_width(&self) -> usize {
@@ -38,26 +221,116 @@ impl<T> Table<T> {
         self.height
     }
 
+    /// The Array that backs this table
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    pub(crate) fn get_array(&self) -> &Array<T> {
+        &self.array
+    }
+
+    /// The array index of the element at (0, 0) for strided tables. For tiled and Morton tables, this
+    /// is the offset that is added to the indices computed by the indexing of this table.
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Describes how the coordinates of this table are mapped to array indices, relative to get_offset()
+    pub fn get_indexing(&self) -> TableIndexing {
+        self.indexing
+    }
+
+    /// The difference between the array indices of (x + 1, y) and (x, y). This will panic if this table
+    /// uses the tiled or Morton layout, in which case the difference is not constant.
+    pub fn get_column_stride(&self) -> usize {
+        match self.indexing {
+            TableIndexing::Strided { column_stride, .. } => column_stride,
+            indexing => panic!("The table indexing {:?} doesn't have a column stride", indexing)
+        }
+    }
+
+    /// The difference between the array indices of (x, y + 1) and (x, y). This will panic if this table
+    /// uses the tiled or Morton layout, in which case the difference is not constant.
+    pub fn get_row_stride(&self) -> usize {
+        match self.indexing {
+            TableIndexing::Strided { row_stride, .. } => row_stride,
+            indexing => panic!("The table indexing {:?} doesn't have a row stride", indexing)
+        }
+    }
+
+    /// Checks if the elements of each row are stored directly after each other in the backing Array,
+    /// which is the case when the column stride is 1.
+    pub fn has_contiguous_rows(&self) -> bool {
+        matches!(self.indexing, TableIndexing::Strided { column_stride: 1, .. })
+    }
+
+    /// Checks if the elements of each column are stored directly after each other in the backing Array,
+    /// which is the case when the row stride is 1 (for instance when the layout is column-major).
+    pub fn has_contiguous_columns(&self) -> bool {
+        matches!(self.indexing, TableIndexing::Strided { row_stride: 1, .. })
+    }
+
+    /// Checks if the rows of this table are stored directly after each other in the backing Array,
+    /// which is the case when the column stride is 1 and the row stride equals the width.
+    pub fn is_contiguous(&self) -> bool {
+        self.indexing == TableIndexing::Strided { column_stride: 1, row_stride: self.width }
+    }
+
     pub fn unchecked_index_for(&self, x: usize, y: usize) -> usize {
-        x + y * self.width
+        self.offset + self.indexing.index_for(x, y)
+    }
+
+    /// Calls the given function with the start index and length of every run of consecutive array
+    /// indices that store the elements from min_x to max_x (inclusive) in the row at y.
+    fn for_each_row_run<F: FnMut(usize, usize)>(&self, y: usize, min_x: usize, max_x: usize, mut function: F){
+        let mut x = min_x;
+        while x <= max_x {
+            let length = min(self.indexing.row_run_length(x), max_x - x + 1);
+            function(self.unchecked_index_for(x, y), length);
+            x += length;
+        }
     }
 
     /// Gets the array index for the given x and y. This will panic if x or y
     /// is outside this table.
     pub fn index_for(&self, x: usize, y: usize) -> usize {
+        match self.try_index_for(x, y) {
+            Ok(index) => index,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the array index for the given x and y. This will return an error if x or y
+    /// is outside this table.
+    pub fn try_index_for(&self, x: usize, y: usize) -> Result<usize, MemoryStructuresError> {
         if x >= self.width || y >= self.height {
-            panic!("x is {}, width is {}, y is {} and height is {}", x, self.width, y, self.height);
+            return Err(MemoryStructuresError::CoordinatesOutOfBounds { x, y, width: self.width, height: self.height });
         }
 
         // If x and y are within range, which has been checked in the new method,
         // the resulting index must be safe
-        self.unchecked_index_for(x, y)
+        Ok(self.unchecked_index_for(x, y))
+    }
+
+    fn check_x(&self, x: usize){
+        if x >= self.width {
+            panic!("x is {} and width is {}", x, self.width);
+        }
+    }
+
+    fn check_y(&self, y: usize){
+        if y >= self.height {
+            panic!("y is {} and height is {}", y, self.height);
+        }
     }
 
     pub fn set(&self, x: usize, y: usize, value: T){
         self.array.set_unchecked(self.index_for(x, y), value);
     }
 
+    pub fn try_set(&self, x: usize, y: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.array.set_unchecked(self.try_index_for(x, y)?, value);
+        Ok(())
+    }
+
     pub fn set_unchecked(&self, x: usize, y: usize, value: T){
         self.array.set_unchecked(self.unchecked_index_for(x, y), value);
     }
@@ -66,6 +339,10 @@ impl<T> Table<T> {
         self.array.get_unchecked_ref(self.index_for(x, y))
     }
 
+    pub fn try_get_ref(&self, x: usize, y: usize) -> Result<&T, MemoryStructuresError> {
+        Ok(self.array.get_unchecked_ref(self.try_index_for(x, y)?))
+    }
+
     pub fn get_unchecked_ref(&self, x: usize, y: usize) -> &T {
         self.array.get_unchecked_ref(self.unchecked_index_for(x, y))
     }
@@ -74,15 +351,23 @@ impl<T> Table<T> {
         self.array.get_unchecked_mut_ref(self.index_for(x, y))
     }
 
+    pub fn try_get_mut_ref(&self, x: usize, y: usize) -> Result<&mut T, MemoryStructuresError> {
+        Ok(self.array.get_unchecked_mut_ref(self.try_index_for(x, y)?))
+    }
+
     pub fn get_unchecked_mut_ref(&self, x: usize, y: usize) -> &mut T {
         self.array.get_unchecked_mut_ref(self.unchecked_index_for(x, y))
     }
 }
 
-use std::fmt::Debug;
+use crate::array::{DEBUG_ELEMENT_LIMIT, TruncatedList};
+
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
 
 impl<T: Debug + Copy> Table<T> {
 
+    #[deprecated(note = "Use the Display implementation of Table instead")]
     pub fn print(&self){
         for y in 0..self.height {
             let mut vector = Vec::with_capacity(self.width);
@@ -94,46 +379,297 @@ impl<T: Debug + Copy> Table<T> {
     }
 }
 
+impl<T> Table<T> {
+
+    /// Creates an iterator over references to the elements of the row at the given y-coordinate,
+    /// which must be valid.
+    pub(crate) fn row_iter(&self, y: usize) -> impl Iterator<Item = &T> {
+        (0..self.width).map(move |x| self.get_unchecked_ref(x, y))
+    }
+}
+
+/// Formats the rows of a table as a Debug list that shows at most DEBUG_ELEMENT_LIMIT rows.
+struct TruncatedRows<'a, T>(&'a Table<T>);
+
+impl<'a, T: Debug> Debug for TruncatedRows<'a, T> {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let table = self.0;
+        let mut list = f.debug_list();
+        for y in 0..min(table.height, DEBUG_ELEMENT_LIMIT) {
+            let row: Vec<&T> = table.row_iter(y).take(DEBUG_ELEMENT_LIMIT + 1).collect();
+            list.entry(&TruncatedList(&row, table.width));
+        }
+        if table.height > DEBUG_ELEMENT_LIMIT {
+            list.entry(&format_args!("... {} more rows", table.height - DEBUG_ELEMENT_LIMIT));
+        }
+        list.finish()
+    }
+}
+
+impl<T: Debug> Debug for Table<T> {
+
+    /// Shows the dimensions and the rows of this table. At most 32 rows and 32 columns will be shown.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rows", &TruncatedRows(self))
+            .finish()
+    }
+}
+
+impl<T: Display> Display for Table<T> {
+
+    /// Shows all elements of this table in a grid with one row per line. The elements are right-aligned
+    /// in columns, which are separated by a space. The precision of the formatter (like in {:.2}) is
+    /// applied to every element.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<String> = (0..self.height).flat_map(|y| self.row_iter(y)).map(|element| {
+            match f.precision() {
+                Some(precision) => format!("{:.*}", precision, element),
+                None => format!("{}", element)
+            }
+        }).collect();
+
+        let mut column_widths = vec![0; self.width];
+        for (index, cell) in cells.iter().enumerate() {
+            let column_width = &mut column_widths[index % self.width];
+            *column_width = std::cmp::max(*column_width, cell.chars().count());
+        }
+
+        for (row_index, row) in cells.chunks(self.width).enumerate() {
+            if row_index > 0 {
+                writeln!(f)?;
+            }
+            for (x, cell) in row.iter().enumerate() {
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{:>width$}", cell, width = column_widths[x])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone> Clone for Table<T> {
+
+    /// Creates a new table with the same dimensions that owns a copy of the elements of this table.
+    /// The clone is always contiguous and starts at index 0, even if this table is a sub table.
+    fn clone(&self) -> Table<T> {
+        let mut elements = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            elements.extend(self.row_iter(y).cloned());
+        }
+        Table::new(Array::from_vec(elements), self.width, self.height)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Table<T> {
+
+    /// Two tables are equal when they have the same width, the same height and equal elements.
+    /// The layout in the backing Arrays doesn't matter, so a sub table can be equal to a normal table.
+    fn eq(&self, other: &Table<T>) -> bool {
+        self.width == other.width && self.height == other.height &&
+            (0..self.height).all(|y| self.row_iter(y).eq(other.row_iter(y)))
+    }
+}
+
+impl<T: Eq> Eq for Table<T> {}
+
+impl<T: Hash> Hash for Table<T> {
+
+    fn hash<H: Hasher>(&self, state: &mut H){
+        self.width.hash(state);
+        self.height.hash(state);
+        for y in 0..self.height {
+            for element in self.row_iter(y) {
+                element.hash(state);
+            }
+        }
+    }
+}
+
 impl<T: Copy> Table<T> {
 
     pub fn get(&self, x: usize, y: usize) -> T {
         self.array.get_unchecked(self.index_for(x, y))
     }
 
+    pub fn try_get(&self, x: usize, y: usize) -> Result<T, MemoryStructuresError> {
+        Ok(self.array.get_unchecked(self.try_index_for(x, y)?))
+    }
+
     pub fn get_unchecked(&self, x: usize, y: usize) -> T {
         self.array.get_unchecked(self.unchecked_index_for(x, y))
     }
 
     pub fn set_row(&self, y: usize, value: T){
-        if y >= self.height {
-            panic!("y is {} and height is {}", y, self.height);
-        }
+        self.check_y(y);
         self.set_unchecked_row(y, value);
     }
 
+    pub fn try_set_row(&self, y: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.try_index_for(0, y)?;
+        self.set_unchecked_row(y, value);
+        Ok(())
+    }
+
     pub fn set_unchecked_row(&self, y: usize, value: T){
-        let start_index = self.unchecked_index_for(0, y);
-        self.array.set_some(start_index, self.width, value);
+        self.for_each_row_run(y, 0, self.width - 1, |start_index, length| self.array.set_some(start_index, length, value));
     }
 
     pub fn set_column(&self, x: usize, value: T){
-        if x >= self.width {
-            panic!("x is {} and width is {}", x, self.width);
-        }
+        self.check_x(x);
         self.set_unchecked_column(x, value);
     }
 
+    pub fn try_set_column(&self, x: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.try_index_for(x, 0)?;
+        self.set_unchecked_column(x, value);
+        Ok(())
+    }
+
     pub fn set_unchecked_column(&self, x: usize, value: T){
-        let mut index = x;
-        self.array.set_unchecked(index, value);
-        for _ in 1..self.height {
-            index += self.height;
-            self.array.set_unchecked(index, value);
+        if self.has_contiguous_columns() {
+            self.array.set_some(self.unchecked_index_for(x, 0), self.height, value);
+        } else {
+            for y in 0..self.height {
+                self.set_unchecked(x, y, value);
+            }
         }
     }
 
     pub fn set_all(&self, value: T){
-        self.array.set_some(0, self.bound, value);
+        if self.is_contiguous() {
+            self.array.set_some(self.offset, self.width * self.height, value);
+        } else if self.has_contiguous_columns() {
+            for x in 0..self.width {
+                self.set_unchecked_column(x, value);
+            }
+        } else {
+            for y in 0..self.height {
+                self.set_unchecked_row(y, value);
+            }
+        }
+    }
+
+    /// Creates a Vec containing copies of the elements in the row at the given y-coordinate.
+    /// This will panic if y is not smaller than the height of this table.
+    pub fn get_row(&self, y: usize) -> Vec<T> {
+        self.check_y(y);
+        self.get_unchecked_row(y)
+    }
+
+    pub fn get_unchecked_row(&self, y: usize) -> Vec<T> {
+        (0..self.width).map(|x| self.get_unchecked(x, y)).collect()
+    }
+
+    /// Creates a Vec containing copies of the elements in the column at the given x-coordinate.
+    /// This will panic if x is not smaller than the width of this table.
+    pub fn get_column(&self, x: usize) -> Vec<T> {
+        self.check_x(x);
+        self.get_unchecked_column(x)
+    }
+
+    pub fn get_unchecked_column(&self, x: usize) -> Vec<T> {
+        (0..self.height).map(|y| self.get_unchecked(x, y)).collect()
+    }
+
+    /// Copies all elements of the row at source_y to the row at dest_y.
+    /// This will panic if either row is outside this table.
+    pub fn copy_row(&self, source_y: usize, dest_y: usize){
+        self.check_y(source_y);
+        self.check_y(dest_y);
+        self.copy_unchecked_row(source_y, dest_y);
+    }
+
+    pub fn copy_unchecked_row(&self, source_y: usize, dest_y: usize){
+        for x in 0..self.width {
+            self.set_unchecked(x, dest_y, self.get_unchecked(x, source_y));
+        }
+    }
+
+    /// Copies all elements of the column at source_x to the column at dest_x.
+    /// This will panic if either column is outside this table.
+    pub fn copy_column(&self, source_x: usize, dest_x: usize){
+        self.check_x(source_x);
+        self.check_x(dest_x);
+        self.copy_unchecked_column(source_x, dest_x);
+    }
+
+    pub fn copy_unchecked_column(&self, source_x: usize, dest_x: usize){
+        for y in 0..self.height {
+            self.set_unchecked(dest_x, y, self.get_unchecked(source_x, y));
+        }
+    }
+
+    /// Swaps the contents of the rows at y1 and y2.
+    /// This will panic if either row is outside this table.
+    pub fn swap_rows(&self, y1: usize, y2: usize){
+        self.check_y(y1);
+        self.check_y(y2);
+        self.swap_unchecked_rows(y1, y2);
+    }
+
+    pub fn swap_unchecked_rows(&self, y1: usize, y2: usize){
+        if y1 != y2 {
+            for x in 0..self.width {
+                let old_value = self.get_unchecked(x, y1);
+                self.set_unchecked(x, y1, self.get_unchecked(x, y2));
+                self.set_unchecked(x, y2, old_value);
+            }
+        }
+    }
+
+    /// Swaps the contents of the columns at x1 and x2.
+    /// This will panic if either column is outside this table.
+    pub fn swap_columns(&self, x1: usize, x2: usize){
+        self.check_x(x1);
+        self.check_x(x2);
+        self.swap_unchecked_columns(x1, x2);
+    }
+
+    pub fn swap_unchecked_columns(&self, x1: usize, x2: usize){
+        if x1 != x2 {
+            for y in 0..self.height {
+                let old_value = self.get_unchecked(x1, y);
+                self.set_unchecked(x1, y, self.get_unchecked(x2, y));
+                self.set_unchecked(x2, y, old_value);
+            }
+        }
+    }
+}
+
+impl<T: AddAssign + Copy> Table<T> {
+
+    /// Increases all elements in the row at the given y-coordinate by the given amount.
+    /// This will panic if y is not smaller than the height of this table.
+    pub fn add_row(&self, y: usize, amount: T){
+        self.check_y(y);
+        self.add_unchecked_row(y, amount);
+    }
+
+    pub fn add_unchecked_row(&self, y: usize, amount: T){
+        self.for_each_row_run(y, 0, self.width - 1, |start_index, length| self.array.add_unchecked_some(start_index, length, amount));
+    }
+
+    /// Increases all elements in the column at the given x-coordinate by the given amount.
+    /// This will panic if x is not smaller than the width of this table.
+    pub fn add_column(&self, x: usize, amount: T){
+        self.check_x(x);
+        self.add_unchecked_column(x, amount);
+    }
+
+    pub fn add_unchecked_column(&self, x: usize, amount: T){
+        if self.has_contiguous_columns() {
+            self.array.add_unchecked_some(self.unchecked_index_for(x, 0), self.height, amount);
+        } else {
+            for y in 0..self.height {
+                *self.get_unchecked_mut_ref(x, y) += amount;
+            }
+        }
     }
 }
 
@@ -144,8 +680,14 @@ impl<T: Add + AddAssign + Copy> Graphics2D<T> for Table<T> {
     }
 
     fn add_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, amount: T){
-        for y in min_y..=max_y {
-            self.array.add_unchecked_some(self.unchecked_index_for(min_x, y), max_x - min_x + 1, amount);
+        if self.has_contiguous_columns() {
+            for x in min_x..=max_x {
+                self.array.add_unchecked_some(self.unchecked_index_for(x, min_y), max_y - min_y + 1, amount);
+            }
+        } else {
+            for y in min_y..=max_y {
+                self.for_each_row_run(y, min_x, max_x, |start_index, length| self.array.add_unchecked_some(start_index, length, amount));
+            }
         }
     }
 
@@ -0,0 +1,226 @@
+use crate::Array;
+use crate::MemoryStructuresError;
+use crate::Table;
+
+use std::cmp::{max, min};
+
+/// The width and height of the blocks that are processed one at a time by the transformations. When
+/// a transformation reads rows and writes columns (or vice versa), processing small blocks keeps
+/// both the source and destination rows in the cache.
+const BLOCK_SIZE: usize = 32;
+
+/// Calls the given function for every coordinate in the rectangle with the given width and height,
+/// block by block.
+fn for_each_blocked<F: FnMut(usize, usize)>(width: usize, height: usize, mut function: F){
+    for block_y in (0..height).step_by(BLOCK_SIZE) {
+        for block_x in (0..width).step_by(BLOCK_SIZE) {
+            for y in block_y..min(block_y + BLOCK_SIZE, height) {
+                for x in block_x..min(block_x + BLOCK_SIZE, width) {
+                    function(x, y);
+                }
+            }
+        }
+    }
+}
+
+/// The y-axis of tables points downwards (as in the Display implementation), so rotating clockwise
+/// moves the top row to the right column. All transformations that create a new table return an owning
+/// row-major table, regardless of the layout of this table.
+impl<T: Copy> Table<T> {
+
+    /// Creates a new table with the given width and height whose element at (x, y) is the element of
+    /// this table at source(x, y).
+    fn remapped<F: Fn(usize, usize) -> (usize, usize)>(&self, width: usize, height: usize, source: F) -> Table<T> {
+        let result = Table::new(Array::create_garbage(width * height), width, height);
+        for_each_blocked(width, height, |x, y| {
+            let (source_x, source_y) = source(x, y);
+            result.set_unchecked(x, y, self.get_unchecked(source_x, source_y));
+        });
+        result
+    }
+
+    fn try_check_square(&self) -> Result<(), MemoryStructuresError> {
+        if self.get_width() != self.get_height() {
+            return Err(MemoryStructuresError::NotSquare { width: self.get_width(), height: self.get_height() });
+        }
+        Ok(())
+    }
+
+    fn swap_unchecked(&self, x1: usize, y1: usize, x2: usize, y2: usize){
+        let old_value = self.get_unchecked(x1, y1);
+        self.set_unchecked(x1, y1, self.get_unchecked(x2, y2));
+        self.set_unchecked(x2, y2, old_value);
+    }
+
+    /// Creates a new table whose element at (x, y) is the element of this table at (y, x).
+    pub fn transpose(&self) -> Table<T> {
+        self.remapped(self.get_height(), self.get_width(), |x, y| (y, x))
+    }
+
+    /// Transposes this table without creating a new table. This will panic if this table is not square.
+    pub fn transpose_in_place(&self){
+        if let Err(error) = self.try_transpose_in_place() {
+            panic!("{}", error);
+        }
+    }
+
+    /// Transposes this table without creating a new table, just like transpose_in_place.
+    /// Instead of panicking, this will return an error if this table is not square.
+    pub fn try_transpose_in_place(&self) -> Result<(), MemoryStructuresError> {
+        self.try_check_square()?;
+        let size = self.get_width();
+
+        // Swap every block above the diagonal with its mirrored block below the diagonal
+        for block_y in (0..size).step_by(BLOCK_SIZE) {
+            for block_x in (block_y..size).step_by(BLOCK_SIZE) {
+                for y in block_y..min(block_y + BLOCK_SIZE, size) {
+                    for x in max(block_x, y + 1)..min(block_x + BLOCK_SIZE, size) {
+                        self.swap_unchecked(x, y, y, x);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a new table that contains this table rotated 90 degrees clockwise.
+    pub fn rotate_90(&self) -> Table<T> {
+        let height = self.get_height();
+        self.remapped(height, self.get_width(), |x, y| (y, height - 1 - x))
+    }
+
+    /// Creates a new table that contains this table rotated 180 degrees.
+    pub fn rotate_180(&self) -> Table<T> {
+        let (width, height) = (self.get_width(), self.get_height());
+        self.remapped(width, height, |x, y| (width - 1 - x, height - 1 - y))
+    }
+
+    /// Creates a new table that contains this table rotated 270 degrees clockwise (which is 90 degrees
+    /// counterclockwise).
+    pub fn rotate_270(&self) -> Table<T> {
+        let width = self.get_width();
+        self.remapped(self.get_height(), width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Rotates this table 90 degrees clockwise without creating a new table. This will panic if this
+    /// table is not square.
+    pub fn rotate_90_in_place(&self){
+        if let Err(error) = self.try_rotate_90_in_place() {
+            panic!("{}", error);
+        }
+    }
+
+    /// Rotates this table 90 degrees clockwise without creating a new table, just like rotate_90_in_place.
+    /// Instead of panicking, this will return an error if this table is not square.
+    pub fn try_rotate_90_in_place(&self) -> Result<(), MemoryStructuresError> {
+        self.try_transpose_in_place()?;
+        self.flip_horizontal_in_place();
+        Ok(())
+    }
+
+    /// Rotates this table 180 degrees without creating a new table. Unlike the other rotations, this is
+    /// also possible for tables that are not square.
+    pub fn rotate_180_in_place(&self){
+        let (width, height) = (self.get_width(), self.get_height());
+        for y in 0..height.div_ceil(2) {
+
+            // In the middle row of a table with an odd height, only the left half must be swapped
+            let end_x = if 2 * y + 1 == height { width / 2 } else { width };
+            for x in 0..end_x {
+                self.swap_unchecked(x, y, width - 1 - x, height - 1 - y);
+            }
+        }
+    }
+
+    /// Rotates this table 270 degrees clockwise without creating a new table. This will panic if this
+    /// table is not square.
+    pub fn rotate_270_in_place(&self){
+        if let Err(error) = self.try_rotate_270_in_place() {
+            panic!("{}", error);
+        }
+    }
+
+    /// Rotates this table 270 degrees clockwise without creating a new table, just like
+    /// rotate_270_in_place. Instead of panicking, this will return an error if this table is not square.
+    pub fn try_rotate_270_in_place(&self) -> Result<(), MemoryStructuresError> {
+        self.try_transpose_in_place()?;
+        self.flip_vertical_in_place();
+        Ok(())
+    }
+
+    /// Creates a new table that contains this table mirrored horizontally: the left column becomes the
+    /// right column.
+    pub fn flip_horizontal(&self) -> Table<T> {
+        let width = self.get_width();
+        self.remapped(width, self.get_height(), |x, y| (width - 1 - x, y))
+    }
+
+    /// Creates a new table that contains this table mirrored vertically: the top row becomes the bottom row.
+    pub fn flip_vertical(&self) -> Table<T> {
+        let height = self.get_height();
+        self.remapped(self.get_width(), height, |x, y| (x, height - 1 - y))
+    }
+
+    /// Mirrors this table horizontally without creating a new table.
+    pub fn flip_horizontal_in_place(&self){
+        let width = self.get_width();
+        for y in 0..self.get_height() {
+            for x in 0..width / 2 {
+                self.swap_unchecked(x, y, width - 1 - x, y);
+            }
+        }
+    }
+
+    /// Mirrors this table vertically without creating a new table.
+    pub fn flip_vertical_in_place(&self){
+        let height = self.get_height();
+        for y in 0..height / 2 {
+            self.swap_unchecked_rows(y, height - 1 - y);
+        }
+    }
+
+    /// Creates a new table that contains a copy of the rectangle of this table with its top-left corner
+    /// at (x, y) and the given width and height. This will panic if the rectangle is empty or doesn't fit
+    /// in this table.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Table<T> {
+        match self.try_crop(x, y, width, height) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new table that contains a copy of a rectangle of this table, just like crop.
+    /// Instead of panicking, this will return an error if the rectangle is empty or doesn't fit.
+    pub fn try_crop(&self, x: usize, y: usize, width: usize, height: usize) -> Result<Table<T>, MemoryStructuresError> {
+        // The sub table is dropped before this method returns, so it can't outlive this table
+        let region = unsafe { self.try_sub_table(x, y, width, height)? };
+        Ok(region.remapped(width, height, |x, y| (x, y)))
+    }
+
+    /// Creates a new table that contains this table surrounded by a border with the given value. The border
+    /// is left columns wide on the left side, top rows high on the top side, and so on. This will panic if
+    /// the size of the new table would overflow.
+    pub fn pad(&self, left: usize, top: usize, right: usize, bottom: usize, value: T) -> Table<T> {
+        match self.try_pad(left, top, right, bottom, value) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new table that contains this table surrounded by a border, just like pad.
+    /// Instead of panicking, this will return an error if the size of the new table would overflow.
+    pub fn try_pad(&self, left: usize, top: usize, right: usize, bottom: usize, value: T) -> Result<Table<T>, MemoryStructuresError> {
+        let width = self.get_width().checked_add(left).and_then(|width| width.checked_add(right));
+        let height = self.get_height().checked_add(top).and_then(|height| height.checked_add(bottom));
+        let (width, height) = width.zip(height).ok_or(MemoryStructuresError::Overflow)?;
+        let size = width.checked_mul(height).ok_or(MemoryStructuresError::Overflow)?;
+
+        let result = Table::new(Array::create_filled(size, value), width, height);
+        for y in 0..self.get_height() {
+            for x in 0..self.get_width() {
+                result.set_unchecked(left + x, top + y, self.get_unchecked(x, y));
+            }
+        }
+        Ok(result)
+    }
+}
@@ -85,4 +85,50 @@ impl Saturating for i128 {
     fn saturating_add(&self, other: Self) -> Self {
         (*self).saturating_add(other)
     }
-}
\ No newline at end of file
+}
+
+/// Converts a number to an f64, which may lose precision for large integers.
+pub trait ToF64 {
+
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(usize, isize, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Converts an f64 to a number. Integer types round to the nearest integer and saturate at their
+/// minimum and maximum values (NaN becomes 0).
+pub trait FromF64 {
+
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_from_f64 {
+    ($rounding:ident, $($t:ty),*) => {
+        $(
+            impl FromF64 for $t {
+
+                fn from_f64(value: f64) -> Self {
+                    impl_from_f64!(@convert $rounding, value, $t)
+                }
+            }
+        )*
+    };
+    (@convert round, $value:expr, $t:ty) => { $value.round() as $t };
+    (@convert exact, $value:expr, $t:ty) => { $value as $t };
+}
+
+impl_from_f64!(round, usize, isize, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+impl_from_f64!(exact, f32, f64);
@@ -0,0 +1,211 @@
+use crate::Graphics2D;
+use crate::MemoryStructuresError;
+use crate::Table;
+use crate::summed_area::check_rect;
+
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign};
+
+/// A view on a rectangular part of a Table that is returned by the split methods of Table.
+/// Unlike the Table returned by sub_table, a TableView borrows the Table it was split from,
+/// so the borrow checker ensures that the views of a split are disjoint and don't outlive
+/// the original table. Each view can be sent to another thread, which makes it possible
+/// to process a Table in parallel without any unsafe code (for instance using std::thread::scope).
+///
+/// A TableView only exposes operations that check their coordinates against the bounds of the
+/// view, so a view can never touch the elements of the other views. For the same reason, the
+/// table of a view can't be accessed or moved out of the view:
+///
+/// ```compile_fail
+/// use memory_structures::{Array, Table};
+///
+/// let mut table = Table::new(Array::create_filled(16, 0), 4, 4);
+/// let other = Table::new(Array::create_filled(4, 0), 2, 2);
+/// let mut views = table.split_tiles_mut(2, 2);
+/// let escaped = std::mem::replace(&mut *views[0], other);
+/// ```
+///
+/// ```compile_fail
+/// use memory_structures::{Array, Table};
+///
+/// let mut table = Table::new(Array::create_filled(16, 0), 4, 4);
+/// let views = table.split_tiles_mut(2, 2);
+/// views[0].set_unchecked(3, 3, 1);
+/// ```
+///
+/// A view can only be sent to another thread if its elements can be:
+///
+/// ```compile_fail
+/// use memory_structures::{Array, Table};
+/// use std::rc::Rc;
+///
+/// let mut table = Table::new(Array::from_vec(vec![Rc::new(0); 16]), 4, 4);
+/// let mut views = table.split_tiles_mut(2, 2);
+/// let view = views.pop().unwrap();
+/// std::thread::scope(|scope| {
+///     scope.spawn(move || view.get_width());
+/// });
+/// ```
+///
+/// TableView implements Graphics2D, but its add_unchecked and add_unchecked_rect methods check
+/// the bounds anyway and panic when they are violated.
+pub struct TableView<'a, T> {
+
+    table: Table<T>,
+
+    x: usize,
+    y: usize,
+
+    // Table<T> is Send for any T, so this also makes sure that the view is only Send when T is
+    _borrow: PhantomData<&'a mut [T]>
+}
+
+impl<'a, T> TableView<'a, T> {
+
+    pub(crate) fn new(table: Table<T>, x: usize, y: usize) -> TableView<'a, T> {
+        TableView {
+            table,
+            x,
+            y,
+            _borrow: PhantomData
+        }
+    }
+
+    /// The x-coordinate of the left column of this view in the table it was split from
+    pub fn get_x(&self) -> usize {
+        self.x
+    }
+
+    /// The y-coordinate of the top row of this view in the table it was split from
+    pub fn get_y(&self) -> usize {
+        self.y
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.table.get_width()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.table.get_height()
+    }
+
+    /// Sets the element at (x, y) of this view (relative to the top-left corner of this view).
+    /// This will panic if x or y is outside this view.
+    pub fn set(&self, x: usize, y: usize, value: T){
+        self.table.set(x, y, value);
+    }
+
+    /// Sets the element at (x, y) of this view. This will return an error if x or y is outside this view.
+    pub fn try_set(&self, x: usize, y: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.table.try_set(x, y, value)
+    }
+
+    /// Gets a reference to the element at (x, y) of this view. This will panic if x or y is outside this view.
+    pub fn get_ref(&self, x: usize, y: usize) -> &T {
+        self.table.get_ref(x, y)
+    }
+
+    /// Gets a reference to the element at (x, y) of this view. This will return an error if x or y is
+    /// outside this view.
+    pub fn try_get_ref(&self, x: usize, y: usize) -> Result<&T, MemoryStructuresError> {
+        self.table.try_get_ref(x, y)
+    }
+
+    /// Gets a mutable reference to the element at (x, y) of this view. This will panic if x or y is
+    /// outside this view.
+    pub fn get_mut_ref(&mut self, x: usize, y: usize) -> &mut T {
+        self.table.get_mut_ref(x, y)
+    }
+
+    /// Gets a mutable reference to the element at (x, y) of this view. This will return an error if
+    /// x or y is outside this view.
+    pub fn try_get_mut_ref(&mut self, x: usize, y: usize) -> Result<&mut T, MemoryStructuresError> {
+        self.table.try_get_mut_ref(x, y)
+    }
+}
+
+impl<'a, T: Copy> TableView<'a, T> {
+
+    /// Gets the element at (x, y) of this view. This will panic if x or y is outside this view.
+    pub fn get(&self, x: usize, y: usize) -> T {
+        self.table.get(x, y)
+    }
+
+    /// Gets the element at (x, y) of this view. This will return an error if x or y is outside this view.
+    pub fn try_get(&self, x: usize, y: usize) -> Result<T, MemoryStructuresError> {
+        self.table.try_get(x, y)
+    }
+
+    /// Sets all elements in row y of this view to the given value. This will panic if y is outside this view.
+    pub fn set_row(&self, y: usize, value: T){
+        self.table.set_row(y, value);
+    }
+
+    /// Sets all elements in row y of this view to the given value. This will return an error if y is
+    /// outside this view.
+    pub fn try_set_row(&self, y: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.table.try_set_row(y, value)
+    }
+
+    /// Sets all elements in column x of this view to the given value. This will panic if x is outside this view.
+    pub fn set_column(&self, x: usize, value: T){
+        self.table.set_column(x, value);
+    }
+
+    /// Sets all elements in column x of this view to the given value. This will return an error if x is
+    /// outside this view.
+    pub fn try_set_column(&self, x: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.table.try_set_column(x, value)
+    }
+
+    /// Sets all elements of this view to the given value
+    pub fn set_all(&self, value: T){
+        self.table.set_all(value);
+    }
+
+    /// Copies the elements in row y of this view to a Vec. This will panic if y is outside this view.
+    pub fn get_row(&self, y: usize) -> Vec<T> {
+        self.table.get_row(y)
+    }
+
+    /// Copies the elements in column x of this view to a Vec. This will panic if x is outside this view.
+    pub fn get_column(&self, x: usize) -> Vec<T> {
+        self.table.get_column(x)
+    }
+}
+
+impl<'a, T: AddAssign + Copy> TableView<'a, T> {
+
+    /// Adds amount to all elements in row y of this view. This will panic if y is outside this view.
+    pub fn add_row(&self, y: usize, amount: T){
+        self.table.add_row(y, amount);
+    }
+
+    /// Adds amount to all elements in column x of this view. This will panic if x is outside this view.
+    pub fn add_column(&self, x: usize, amount: T){
+        self.table.add_column(x, amount);
+    }
+}
+
+impl<'a, T: Add + AddAssign + Copy> Graphics2D<T> for TableView<'a, T> {
+
+    fn add_unchecked(&self, x: usize, y: usize, amount: T){
+        let index = self.table.index_for(x, y);
+        self.table.get_array().add_unchecked(index, amount);
+    }
+
+    fn add_unchecked_rect(&self, min_x: usize, min_y: usize, max_x: usize, max_y: usize, amount: T){
+        if let Err(error) = check_rect(min_x, min_y, max_x, max_y, self.get_width(), self.get_height()) {
+            panic!("{}", error);
+        }
+        self.table.add_unchecked_rect(min_x, min_y, max_x, max_y, amount);
+    }
+
+    fn get_width(&self) -> usize {
+        self.table.get_width()
+    }
+
+    fn get_height(&self) -> usize {
+        self.table.get_height()
+    }
+}
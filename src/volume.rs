@@ -0,0 +1,268 @@
+use crate::Array;
+use crate::Graphics3D;
+use crate::MemoryStructuresError;
+use crate::Table;
+
+use std::ops::AddAssign;
+
+/// One of the 3 axes of a Volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z
+}
+
+/// A 3-dimensional structure that is backed by an Array. The element at (x, y, z) is stored at
+/// array index x + y * width + z * width * height.
+pub struct Volume<T> {
+
+    array: Array<T>,
+
+    width: usize,
+    height: usize,
+    depth: usize
+}
+
+impl<T> Volume<T> {
+
+    pub fn new(array: Array<T>, width: usize, height: usize, depth: usize) -> Volume<T> {
+        match Volume::try_new(array, width, height, depth) {
+            Ok(volume) => volume,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a new Volume with the given width, height and depth that is backed by the given Array.
+    /// Instead of panicking, this will return an error if any of the dimensions is 0 or if the
+    /// array is too small.
+    pub fn try_new(array: Array<T>, width: usize, height: usize, depth: usize) -> Result<Volume<T>, MemoryStructuresError> {
+        if width == 0 || height == 0 || depth == 0 {
+            return Err(MemoryStructuresError::ZeroVolumeDimension { width, height, depth });
+        }
+        let bound = width.checked_mul(height)
+            .and_then(|area| area.checked_mul(depth))
+            .ok_or(MemoryStructuresError::Overflow)?;
+
+        // This test ensures that any operation within the volume bounds will also be within the Array bounds.
+        if array.try_check_bound(bound - 1).is_err() {
+            return Err(MemoryStructuresError::ArrayTooSmall { required_size: bound, size: array.len() });
+        }
+        Ok(Volume {
+            array,
+            width,
+            height,
+            depth
+        })
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Gets the size of this volume along the given axis
+    pub fn get_size(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::X => self.width,
+            Axis::Y => self.height,
+            Axis::Z => self.depth
+        }
+    }
+
+    pub fn unchecked_index_for(&self, x: usize, y: usize, z: usize) -> usize {
+        x + y * self.width + z * self.width * self.height
+    }
+
+    /// Gets the array index for the given x, y and z. This will panic if any of them
+    /// is outside this volume.
+    pub fn index_for(&self, x: usize, y: usize, z: usize) -> usize {
+        match self.try_index_for(x, y, z) {
+            Ok(index) => index,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Gets the array index for the given x, y and z. This will return an error if any of them
+    /// is outside this volume.
+    pub fn try_index_for(&self, x: usize, y: usize, z: usize) -> Result<usize, MemoryStructuresError> {
+        if x >= self.width || y >= self.height || z >= self.depth {
+            return Err(MemoryStructuresError::VolumeCoordinatesOutOfBounds {
+                x, y, z, width: self.width, height: self.height, depth: self.depth
+            });
+        }
+        Ok(self.unchecked_index_for(x, y, z))
+    }
+
+    pub fn set(&self, x: usize, y: usize, z: usize, value: T){
+        self.array.set_unchecked(self.index_for(x, y, z), value);
+    }
+
+    pub fn try_set(&self, x: usize, y: usize, z: usize, value: T) -> Result<(), MemoryStructuresError> {
+        self.array.set_unchecked(self.try_index_for(x, y, z)?, value);
+        Ok(())
+    }
+
+    pub fn set_unchecked(&self, x: usize, y: usize, z: usize, value: T){
+        self.array.set_unchecked(self.unchecked_index_for(x, y, z), value);
+    }
+
+    pub fn get_ref(&self, x: usize, y: usize, z: usize) -> &T {
+        self.array.get_unchecked_ref(self.index_for(x, y, z))
+    }
+
+    pub fn get_unchecked_ref(&self, x: usize, y: usize, z: usize) -> &T {
+        self.array.get_unchecked_ref(self.unchecked_index_for(x, y, z))
+    }
+
+    pub fn get_mut_ref(&self, x: usize, y: usize, z: usize) -> &mut T {
+        self.array.get_unchecked_mut_ref(self.index_for(x, y, z))
+    }
+
+    pub fn get_unchecked_mut_ref(&self, x: usize, y: usize, z: usize) -> &mut T {
+        self.array.get_unchecked_mut_ref(self.unchecked_index_for(x, y, z))
+    }
+
+    /// Creates a Table that is a view on the plane of this volume where the coordinate along the given
+    /// axis equals position. The view shares its data with this volume. The axes of the table are the
+    /// remaining axes of the volume in order: a slice along X has (y, z) coordinates, a slice along Y
+    /// has (x, z) coordinates and a slice along Z has (x, y) coordinates.
+    /// This will panic if position is not smaller than the size of this volume along the axis.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as Array::sharing_copy: the returned table must
+    /// not be used after the Array of this volume has been dropped.
+    pub unsafe fn slice(&self, axis: Axis, position: usize) -> Table<T> {
+        match self.try_slice(axis, position) {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    /// Creates a Table that is a view on a plane of this volume, just like slice.
+    /// Instead of panicking, this will return an error if position is out of bounds.
+    ///
+    /// # Safety
+    /// This method is unsafe for the same reasons as Array::sharing_copy.
+    pub unsafe fn try_slice(&self, axis: Axis, position: usize) -> Result<Table<T>, MemoryStructuresError> {
+        let size = self.get_size(axis);
+        if position >= size {
+            return Err(MemoryStructuresError::IndexOutOfBounds { index: position, size });
+        }
+        let area = self.width * self.height;
+        let array = self.array.sharing_copy();
+        match axis {
+            Axis::X => Table::try_with_strides(array, position, self.height, self.depth, self.width, area),
+            Axis::Y => Table::try_with_strides(array, position * self.width, self.width, self.depth, 1, area),
+            Axis::Z => Table::try_with_strides(array, position * area, self.width, self.height, 1, self.width)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn check_box(&self, min_x: usize, min_y: usize, min_z: usize, max_x: usize, max_y: usize, max_z: usize){
+        if min_x > max_x || min_y > max_y || min_z > max_z {
+            panic!("The minimum corner ({}, {}, {}) is larger than the maximum corner ({}, {}, {})", min_x, min_y, min_z, max_x, max_y, max_z);
+        }
+        self.index_for(max_x, max_y, max_z);
+    }
+}
+
+impl<T: Copy> Volume<T> {
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> T {
+        self.array.get_unchecked(self.index_for(x, y, z))
+    }
+
+    pub fn try_get(&self, x: usize, y: usize, z: usize) -> Result<T, MemoryStructuresError> {
+        Ok(self.array.get_unchecked(self.try_index_for(x, y, z)?))
+    }
+
+    pub fn get_unchecked(&self, x: usize, y: usize, z: usize) -> T {
+        self.array.get_unchecked(self.unchecked_index_for(x, y, z))
+    }
+
+    pub fn set_all(&self, value: T){
+        self.array.set_some(0, self.width * self.height * self.depth, value);
+    }
+
+    /// Sets all elements in the box from (min_x, min_y, min_z) to (max_x, max_y, max_z) (both inclusive)
+    /// to the given value. This will panic if the box is not within this volume or if a minimum
+    /// coordinate is larger than the corresponding maximum coordinate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_box(&self, min_x: usize, min_y: usize, min_z: usize, max_x: usize, max_y: usize, max_z: usize, value: T){
+        self.check_box(min_x, min_y, min_z, max_x, max_y, max_z);
+        for z in min_z..=max_z {
+            for y in min_y..=max_y {
+                self.array.set_some(self.unchecked_index_for(min_x, y, z), max_x - min_x + 1, value);
+            }
+        }
+    }
+
+    /// Sets all elements in the plane where the coordinate along the given axis equals position
+    /// to the given value. This will panic if position is out of bounds.
+    pub fn set_plane(&self, axis: Axis, position: usize, value: T){
+        // The slice is dropped before this method returns, so it can't outlive this volume
+        unsafe { self.slice(axis, position) }.set_all(value);
+    }
+}
+
+impl<T: AddAssign + Copy> Volume<T> {
+
+    /// Increases the element at (x, y, z) by the given amount. This will panic if the coordinates are
+    /// outside this volume.
+    pub fn add(&self, x: usize, y: usize, z: usize, amount: T){
+        *self.get_mut_ref(x, y, z) += amount;
+    }
+
+    /// Increases all elements in the box from (min_x, min_y, min_z) to (max_x, max_y, max_z) (both inclusive)
+    /// by the given amount. This will panic if the box is not within this volume or if a minimum
+    /// coordinate is larger than the corresponding maximum coordinate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_box(&self, min_x: usize, min_y: usize, min_z: usize, max_x: usize, max_y: usize, max_z: usize, amount: T){
+        self.check_box(min_x, min_y, min_z, max_x, max_y, max_z);
+        self.add_unchecked_box(min_x, min_y, min_z, max_x, max_y, max_z, amount);
+    }
+
+    /// Increases all elements in the plane where the coordinate along the given axis equals position
+    /// by the given amount. This will panic if position is out of bounds.
+    pub fn add_plane(&self, axis: Axis, position: usize, amount: T){
+        let plane = unsafe { self.slice(axis, position) };
+        for y in 0..plane.get_height() {
+            plane.add_unchecked_row(y, amount);
+        }
+    }
+}
+
+impl<T: AddAssign + Copy> Graphics3D<T> for Volume<T> {
+
+    fn add_unchecked(&self, x: usize, y: usize, z: usize, amount: T){
+        *self.get_unchecked_mut_ref(x, y, z) += amount;
+    }
+
+    fn add_unchecked_box(&self, min_x: usize, min_y: usize, min_z: usize, max_x: usize, max_y: usize, max_z: usize, amount: T){
+        for z in min_z..=max_z {
+            for y in min_y..=max_y {
+                self.array.add_unchecked_some(self.unchecked_index_for(min_x, y, z), max_x - min_x + 1, amount);
+            }
+        }
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn get_depth(&self) -> usize {
+        self.depth
+    }
+}